@@ -1,27 +1,295 @@
 use std::collections::HashMap;
 
-use xta_lexer::token::Loc;
-use xta_parser::ast::{FunctionDeclStmt, Stmt};
+use xta_diagnostics::diagnostic::Diagnostic;
+use xta_lexer::token::{Loc, Span};
+use xta_parser::ast::{Expr, FunctionDeclStmt, Stmt};
+
+use crate::{scope::Scope, types::Type};
+
+// functions provided by the runtime, callable without a declaration
+pub(crate) const BUILTINS: &[&str] = &["print"];
 
 pub struct Analyzer<'ctx> {
-    functions: HashMap<&'ctx str, FunctionDeclStmt<'ctx>>,
-    errors: Vec<Error>
+    pub(crate) functions: HashMap<&'ctx str, &'ctx FunctionDeclStmt<'ctx>>,
+    pub(crate) scopes: Vec<Scope<'ctx>>,
+    // the return type of the function currently being analyzed
+    pub(crate) return_type: Type,
+    // how many loops enclose the statement currently being analyzed
+    pub(crate) loop_depth: usize,
+    pub errors: Vec<Error>,
+    pub warnings: Vec<Warning>,
 }
 
 impl<'ctx> Analyzer<'ctx> {
-    pub fn new(stmts: Vec<Stmt<'ctx>>) -> Self {
-        // TODO: make analyzer run on the given statements,
-        // create an AST node called file, and give it statements to map
+    pub fn new(stmts: &'ctx [Stmt<'ctx>]) -> Self {
+        let mut analyzer = Self {
+            functions: HashMap::new(),
+            scopes: Vec::new(),
+            return_type: Type::None,
+            loop_depth: 0,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        analyzer.register_functions(stmts);
+        analyzer.check_main();
+
+        // top-level constants live in the outermost scope, visible to every function
+        analyzer.push_block_scope(stmts);
+        for stmt in stmts {
+            if let Stmt::VarDecl(decl) = stmt {
+                if decl.is_const {
+                    analyzer.resolve_stmt(stmt);
+                }
+            }
+        }
+
+        for stmt in stmts {
+            if let Stmt::FunctionDecl(func) = stmt {
+                analyzer.resolve_function(func);
+                analyzer.check_flow(func);
+            }
+        }
+        analyzer.scopes.pop();
+
+        analyzer
+    }
+
+    pub fn get_function(&self, name: &str) -> Option<&'ctx FunctionDeclStmt<'ctx>> {
+        self.functions.get(name).copied()
+    }
+}
+
+// Private functions
+impl<'ctx> Analyzer<'ctx> {
+    // collects every top-level function, only functions and constants are allowed at the top level
+    fn register_functions(&mut self, stmts: &'ctx [Stmt<'ctx>]) {
+        for stmt in stmts {
+            let func = match stmt {
+                Stmt::FunctionDecl(func) => func,
+                Stmt::VarDecl(decl) if decl.is_const => continue,
+                // statements the parser failed on are reported already
+                Stmt::Expr(Expr::Error(_)) => continue,
+                _ => {
+                    self.errors.push(Error::UnexpectedNonFunction { loc: stmt.loc(), span: stmt.span() });
+                    continue;
+                }
+            };
+
+            if let Some(previous) = self.functions.get(func.name) {
+                self.errors.push(Error::DuplicateFunction {
+                    loc: func.loc.clone(),
+                    span: func.name_span,
+                    name: func.name.to_string(),
+                    previous: previous.loc.clone(),
+                    previous_span: previous.name_span,
+                });
+                continue;
+            }
+
+            self.functions.insert(func.name, func);
+        }
+    }
+
+    fn check_main(&mut self) {
+        match self.functions.get("main") {
+            Some(main) if !main.params.is_empty() => {
+                let span = main.params[0].span.to(main.params[main.params.len() - 1].span);
+                self.errors.push(Error::MainWithParams { loc: main.loc.clone(), span });
+            }
+            Some(_) => {}
+            None => self.errors.push(Error::MissingMain),
+        }
     }
 }
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum Error {
     #[error("~ ({loc}) : Unexpected non-function statement")]
-    UnexpectedNonFunction { loc: Loc },
-    // #[error("~ ({loc}) : Expected `{expected}`, found `{found}`")]
-    // Expected { loc: Loc, expected: Token<'a>, found: Token<'a> },
+    UnexpectedNonFunction { loc: Loc, span: Span },
+
+    #[error("~ ({loc}) : Function `{name}` is already declared at ({previous})")]
+    DuplicateFunction { loc: Loc, span: Span, name: String, previous: Loc, previous_span: Span },
+
+    #[error("~ ({loc}) : Parameter `{name}` is already declared at ({previous})")]
+    DuplicateParam { loc: Loc, span: Span, name: String, previous: Loc, previous_span: Span },
+
+    #[error("~ ({loc}) : Functions can only be declared at the top level")]
+    NestedFunction { loc: Loc, span: Span },
+
+    #[error("~ ({loc}) : Use of undeclared identifier `{name}`")]
+    UndeclaredIdentifier { loc: Loc, span: Span, name: String },
+
+    #[error("~ ({loc}) : `{name}` is used before its declaration at ({decl})")]
+    UseBeforeDeclaration { loc: Loc, span: Span, name: String, decl: Loc, decl_span: Span },
+
+    #[error("~ ({loc}) : Call to unknown function `{name}`")]
+    UndefinedFunction { loc: Loc, span: Span, name: String },
+
+    #[error("~ ({loc}) : Function `{name}` must return `{return_type}` on every path")]
+    MissingReturn { loc: Loc, span: Span, name: String, return_type: String },
+
+    #[error("~ ({loc}) : Cannot assign to constant `{name}`")]
+    AssignToConst { loc: Loc, span: Span, name: String, decl: Loc, decl_span: Span },
+
+    #[error("~ ({loc}) : `{keyword}` outside of a loop")]
+    OutsideLoop { loc: Loc, span: Span, keyword: &'static str },
+
+    #[error("~ ({loc}) : Unknown type `{name}`")]
+    UnknownType { loc: Loc, span: Span, name: String },
 
-    // #[error("~ ({loc}) : Expected an identifier, found `{found}`")]
-    // ExpectedId {loc: Loc, found: Token<'a> }
+    #[error("~ ({loc}) : Mismatched types, expected `{expected}`, found `{found}`")]
+    TypeMismatch { loc: Loc, span: Span, expected: Type, found: Type },
+
+    #[error("~ ({loc}) : Function returns `{expected}`, but `{found}` is returned")]
+    ReturnTypeMismatch { loc: Loc, span: Span, expected: Type, found: Type },
+
+    #[error("~ ({loc}) : Function `{name}` takes {expected} argument(s), but {found} were given")]
+    ArgumentCount { loc: Loc, span: Span, name: String, expected: usize, found: usize, decl: Loc, decl_span: Span },
+
+    #[error("~ ({loc}) : Argument `{param}` of `{name}` expects `{expected}`, found `{found}`")]
+    ArgumentTypeMismatch { loc: Loc, span: Span, name: String, param: String, expected: Type, found: Type, decl: Loc, decl_span: Span },
+
+    #[error("~ ({loc}) : Cannot apply `{operator}` to `{left}` and `{right}`")]
+    InvalidOperands { loc: Loc, span: Span, op_span: Span, operator: String, left: Type, right: Type },
+
+    #[error("~ ({loc}) : Cannot apply `{operator}` to `{found}`")]
+    InvalidOperand { loc: Loc, span: Span, operator: String, found: Type },
+
+    #[error("~ ({loc}) : `{name}` is annotated as `{annotated}`, but its value is `{inferred}`")]
+    AnnotationMismatch { loc: Loc, span: Span, name: String, annotated: Type, inferred: Type },
+
+    #[error("~ ({loc}) : `{name}` cannot be bound to a value of type `None`")]
+    NoneBinding { loc: Loc, span: Span, name: String },
+
+    #[error("~ Error: No `main` function was found")]
+    MissingMain,
+
+    #[error("~ ({loc}) : `main` cannot take parameters")]
+    MainWithParams { loc: Loc, span: Span },
+
+    #[error("~ ({loc}) : Cannot assign to this expression")]
+    InvalidAssignTarget { loc: Loc, span: Span },
+
+    #[error("~ ({loc}) : Cannot assign to loop variable `{name}`")]
+    AssignToLoopVar { loc: Loc, span: Span, name: String, decl: Loc, decl_span: Span },
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum Warning {
+    #[error("~ ({loc}) : Warning: Unreachable code")]
+    UnreachableCode { loc: Loc, span: Span },
+}
+
+impl Error {
+    // a stable identifier for the kind of error, explained by `xtac explain <code>`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::UnexpectedNonFunction { .. } => "XA0001",
+            Error::DuplicateFunction { .. } => "XA0002",
+            Error::DuplicateParam { .. } => "XA0003",
+            Error::NestedFunction { .. } => "XA0004",
+            Error::UndeclaredIdentifier { .. } => "XA0005",
+            Error::UseBeforeDeclaration { .. } => "XA0006",
+            Error::UndefinedFunction { .. } => "XA0007",
+            Error::MissingReturn { .. } => "XA0008",
+            Error::AssignToConst { .. } => "XA0009",
+            Error::OutsideLoop { .. } => "XA0010",
+            Error::UnknownType { .. } => "XA0011",
+            Error::TypeMismatch { .. } => "XA0012",
+            Error::ReturnTypeMismatch { .. } => "XA0013",
+            Error::ArgumentCount { .. } => "XA0014",
+            Error::ArgumentTypeMismatch { .. } => "XA0015",
+            Error::InvalidOperands { .. } => "XA0016",
+            Error::InvalidOperand { .. } => "XA0017",
+            Error::AnnotationMismatch { .. } => "XA0018",
+            Error::NoneBinding { .. } => "XA0019",
+            Error::MissingMain => "XA0020",
+            Error::MainWithParams { .. } => "XA0021",
+            Error::InvalidAssignTarget { .. } => "XA0022",
+            Error::AssignToLoopVar { .. } => "XA0023",
+        }
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let diagnostic = match self {
+            Error::UnexpectedNonFunction { span, .. } => Diagnostic::error("Unexpected non-function statement")
+                .with_label(*span, "not allowed at the top level")
+                .with_note("only functions and constants can be declared at the top level"),
+            Error::DuplicateFunction { span, name, previous_span, .. } => Diagnostic::error(format!("Function `{name}` is already declared"))
+                .with_label(*span, "declared again here")
+                .with_secondary(*previous_span, "first declared here"),
+            Error::DuplicateParam { span, name, previous_span, .. } => Diagnostic::error(format!("Parameter `{name}` is already declared"))
+                .with_label(*span, "declared again here")
+                .with_secondary(*previous_span, "first declared here"),
+            Error::NestedFunction { span, .. } => Diagnostic::error("Functions can only be declared at the top level")
+                .with_label(*span, "nested function"),
+            Error::UndeclaredIdentifier { span, name, .. } => Diagnostic::error(format!("Use of undeclared identifier `{name}`"))
+                .with_label(*span, "not found in this scope"),
+            Error::UseBeforeDeclaration { span, name, decl_span, .. } => Diagnostic::error(format!("`{name}` is used before its declaration"))
+                .with_label(*span, "used here")
+                .with_secondary(*decl_span, "declared here"),
+            Error::UndefinedFunction { span, name, .. } => Diagnostic::error(format!("Call to unknown function `{name}`"))
+                .with_label(*span, "no function with this name"),
+            Error::MissingReturn { span, name, return_type, .. } => Diagnostic::error(format!("Function `{name}` must return `{return_type}` on every path"))
+                .with_label(*span, "some paths reach the end of the function"),
+            Error::AssignToConst { span, name, decl_span, .. } => Diagnostic::error(format!("Cannot assign to constant `{name}`"))
+                .with_label(*span, "assigned here")
+                .with_secondary(*decl_span, format!("`{name}` is declared as a constant here")),
+            Error::OutsideLoop { span, keyword, .. } => Diagnostic::error(format!("`{keyword}` outside of a loop"))
+                .with_label(*span, format!("`{keyword}` can only be used inside a loop")),
+            Error::UnknownType { span, name, .. } => Diagnostic::error(format!("Unknown type `{name}`"))
+                .with_label(*span, "unknown type")
+                .with_note("the available types are `Int`, `Double`, `String` and `Bool`"),
+            Error::TypeMismatch { span, expected, found, .. } => Diagnostic::error(format!("Mismatched types, expected `{expected}`, found `{found}`"))
+                .with_label(*span, format!("expected `{expected}`")),
+            Error::ReturnTypeMismatch { span, expected, found, .. } => Diagnostic::error(format!("Function returns `{expected}`, but `{found}` is returned"))
+                .with_label(*span, format!("expected `{expected}`")),
+            Error::ArgumentCount { span, name, expected, found, decl_span, .. } => Diagnostic::error(format!("Function `{name}` takes {expected} argument(s), but {found} were given"))
+                .with_label(*span, format!("{found} argument(s) given"))
+                .with_secondary(*decl_span, format!("`{name}` is declared here")),
+            Error::ArgumentTypeMismatch { span, name, param, expected, found, decl_span, .. } => Diagnostic::error(format!("Argument `{param}` of `{name}` expects `{expected}`, found `{found}`"))
+                .with_label(*span, format!("expected `{expected}`"))
+                .with_secondary(*decl_span, format!("`{param}` is declared here")),
+            Error::InvalidOperands { op_span, operator, left, right, .. } => Diagnostic::error(format!("Cannot apply `{operator}` to `{left}` and `{right}`"))
+                .with_label(*op_span, format!("`{left}` {operator} `{right}`")),
+            Error::InvalidOperand { span, operator, found, .. } => Diagnostic::error(format!("Cannot apply `{operator}` to `{found}`"))
+                .with_label(*span, format!("operand is `{found}`")),
+            Error::AnnotationMismatch { span, name, annotated, inferred, .. } => Diagnostic::error(format!("`{name}` is annotated as `{annotated}`, but its value is `{inferred}`"))
+                .with_label(*span, format!("expected `{annotated}`")),
+            Error::NoneBinding { span, name, .. } => Diagnostic::error(format!("`{name}` cannot be bound to a value of type `None`"))
+                .with_label(*span, "the value is `None`"),
+            Error::MissingMain => Diagnostic::error("No `main` function was found")
+                .with_note("every program starts at `fn main()`"),
+            Error::MainWithParams { span, .. } => Diagnostic::error("`main` cannot take parameters")
+                .with_label(*span, "remove these parameters")
+                .with_suggestion(*span, "", "remove the parameters"),
+            Error::InvalidAssignTarget { span, .. } => Diagnostic::error("Cannot assign to this expression")
+                .with_label(*span, "not a variable")
+                .with_note("only variables can be assigned to, incremented or decremented"),
+            Error::AssignToLoopVar { span, name, decl_span, .. } => Diagnostic::error(format!("Cannot assign to loop variable `{name}`"))
+                .with_label(*span, "assigned here")
+                .with_secondary(*decl_span, format!("`{name}` is the variable of this `for` loop"))
+                .with_note("the loop sets it on every iteration, use `while` to control the counter yourself"),
+        };
+
+        diagnostic.with_code(self.code())
+    }
+}
+
+impl Warning {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Warning::UnreachableCode { .. } => "XW0001",
+        }
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let diagnostic = match self {
+            Warning::UnreachableCode { span, .. } => Diagnostic::warning("Unreachable code")
+                .with_label(*span, "this statement can never run"),
+        };
+
+        diagnostic.with_code(self.code())
+    }
 }
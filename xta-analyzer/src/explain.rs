@@ -0,0 +1,437 @@
+// Long-form explanations of the analyzer's error and warning codes, printed by `xtac explain <code>`
+pub fn explain(code: &str) -> Option<&'static str> {
+    let text = match code {
+        "XA0001" => r#"A statement other than a function or a constant was found at the top level.
+
+Erroneous code example:
+
+    let limit = 10;
+
+    fn main() {
+    }
+
+Only functions and constants can be declared at the top level, move the
+statement into a function or make it a constant:
+
+    const limit = 10;
+
+    fn main() {
+    }
+"#,
+        "XA0002" => r#"Two functions share the same name.
+
+Erroneous code example:
+
+    fn run() {
+    }
+
+    fn run() {
+    }
+
+    fn main() {
+    }
+
+Give each function its own name:
+
+    fn run() {
+    }
+
+    fn run_again() {
+    }
+
+    fn main() {
+    }
+"#,
+        "XA0003" => r#"A function declares two parameters with the same name.
+
+Erroneous code example:
+
+    fn add(a Int, a Int) -> Int {
+        return a + a;
+    }
+
+    fn main() {
+    }
+
+Give each parameter its own name:
+
+    fn add(a Int, b Int) -> Int {
+        return a + b;
+    }
+
+    fn main() {
+    }
+"#,
+        "XA0004" => r#"A function was declared inside another function.
+
+Erroneous code example:
+
+    fn main() {
+        fn helper() {
+        }
+    }
+
+Functions can only be declared at the top level:
+
+    fn helper() {
+    }
+
+    fn main() {
+        helper();
+    }
+"#,
+        "XA0005" => r#"An identifier was used that is not declared in any enclosing scope.
+
+Erroneous code example:
+
+    fn main() {
+        print(count);
+    }
+
+Declare the variable before using it:
+
+    fn main() {
+        let count = 0;
+        print(count);
+    }
+"#,
+        "XA0006" => r#"A variable was used before the statement that declares it.
+
+Erroneous code example:
+
+    fn main() {
+        print(count);
+        let count = 0;
+    }
+
+Move the declaration above its first use:
+
+    fn main() {
+        let count = 0;
+        print(count);
+    }
+"#,
+        "XA0007" => r#"A function was called that is neither declared nor built in.
+
+Erroneous code example:
+
+    fn main() {
+        greet();
+    }
+
+Declare the function at the top level:
+
+    fn greet() {
+        print("hello");
+    }
+
+    fn main() {
+        greet();
+    }
+"#,
+        "XA0008" => r#"A function with a return type can reach its end without returning a value.
+
+Erroneous code example:
+
+    fn sign(a Int) -> Int {
+        if a < 0 {
+            return 0 - 1;
+        }
+    }
+
+    fn main() {
+    }
+
+Return a value on every path:
+
+    fn sign(a Int) -> Int {
+        if a < 0 {
+            return 0 - 1;
+        }
+        return 1;
+    }
+
+    fn main() {
+    }
+"#,
+        "XA0009" => r#"A constant was assigned a new value.
+
+Erroneous code example:
+
+    fn main() {
+        const limit = 10;
+        limit = 20;
+    }
+
+Declare the variable with `let` if it has to change:
+
+    fn main() {
+        let limit = 10;
+        limit = 20;
+    }
+"#,
+        "XA0010" => r#"`break` or `continue` was used outside of a loop.
+
+Erroneous code example:
+
+    fn main() {
+        break;
+    }
+
+Both statements only make sense inside `while`, `for` or `loop`:
+
+    fn main() {
+        loop {
+            break;
+        }
+    }
+"#,
+        "XA0011" => r#"A type name does not name any type.
+
+Erroneous code example:
+
+    fn double(a Integer) -> Int {
+        return a * 2;
+    }
+
+    fn main() {
+    }
+
+The available types are `Int`, `Double`, `String` and `Bool`:
+
+    fn double(a Int) -> Int {
+        return a * 2;
+    }
+
+    fn main() {
+    }
+"#,
+        "XA0012" => r#"An expression has a different type than the one its position requires.
+
+Erroneous code example:
+
+    fn main() {
+        let a = 1;
+        if a {
+            print(a);
+        }
+    }
+
+Conditions must be `Bool`, and `for` bounds must be `Int`:
+
+    fn main() {
+        let a = 1;
+        if a != 0 {
+            print(a);
+        }
+    }
+"#,
+        "XA0013" => r#"A `return` statement returns a value of the wrong type.
+
+Erroneous code example:
+
+    fn name() -> String {
+        return 1;
+    }
+
+    fn main() {
+    }
+
+Return a value of the declared return type:
+
+    fn name() -> String {
+        return "xta";
+    }
+
+    fn main() {
+    }
+"#,
+        "XA0014" => r#"A function was called with the wrong number of arguments.
+
+Erroneous code example:
+
+    fn add(a Int, b Int) -> Int {
+        return a + b;
+    }
+
+    fn main() {
+        print(add(1));
+    }
+
+Pass one argument for every parameter:
+
+    fn add(a Int, b Int) -> Int {
+        return a + b;
+    }
+
+    fn main() {
+        print(add(1, 2));
+    }
+"#,
+        "XA0015" => r#"An argument does not match the type of its parameter.
+
+Erroneous code example:
+
+    fn square(a Int) -> Int {
+        return a * a;
+    }
+
+    fn main() {
+        print(square("2"));
+    }
+
+Pass a value of the parameter's type:
+
+    fn square(a Int) -> Int {
+        return a * a;
+    }
+
+    fn main() {
+        print(square(2));
+    }
+"#,
+        "XA0016" => r#"A binary operator was applied to operands it does not support.
+
+Erroneous code example:
+
+    fn main() {
+        print(1 + "2");
+    }
+
+Arithmetic needs two numbers, `+` also joins two strings:
+
+    fn main() {
+        print(1 + 2);
+    }
+"#,
+        "XA0017" => r#"A unary operator was applied to an operand it does not support.
+
+Erroneous code example:
+
+    fn main() {
+        print(-"one");
+    }
+
+`-` needs a number, `!` needs a `Bool` and `~` needs an `Int`:
+
+    fn main() {
+        print(-1);
+    }
+"#,
+        "XA0018" => r#"A variable's annotated type does not match the type of its value.
+
+Erroneous code example:
+
+    fn main() {
+        let a Int = "one";
+    }
+
+Make the annotation and the value agree:
+
+    fn main() {
+        let a String = "one";
+    }
+"#,
+        "XA0019" => r#"A variable was bound to an expression that produces no value.
+
+Erroneous code example:
+
+    fn log() {
+        print("log");
+    }
+
+    fn main() {
+        let a = log();
+    }
+
+Call functions without a return type as statements:
+
+    fn log() {
+        print("log");
+    }
+
+    fn main() {
+        log();
+    }
+"#,
+        "XA0020" => r#"The program has no `main` function.
+
+Erroneous code example:
+
+    fn start() {
+    }
+
+Execution starts at `main`, so every program has to declare it:
+
+    fn main() {
+    }
+"#,
+        "XA0021" => r#"The `main` function declares parameters.
+
+Erroneous code example:
+
+    fn main(a Int) {
+    }
+
+`main` is called without arguments, so it cannot take any:
+
+    fn main() {
+    }
+"#,
+        "XA0022" => r#"An assignment, `++` or `--` targets something other than a variable.
+
+Erroneous code example:
+
+    fn main() {
+        let a = 1;
+        (a + 1)++;
+    }
+
+Only variables can be updated:
+
+    fn main() {
+        let a = 1;
+        a++;
+    }
+"#,
+        "XA0023" => r#"The variable of a `for` loop was assigned a new value.
+
+Erroneous code example:
+
+    fn main() {
+        for i in 0..10 {
+            i += 1;
+        }
+    }
+
+The loop sets its variable on every iteration. Use `while` to change the
+counter yourself:
+
+    fn main() {
+        let i = 0;
+        while i < 10 {
+            i += 2;
+        }
+    }
+"#,
+        "XW0001" => r#"A statement follows a `return`, `break` or `continue` and can never run.
+
+Erroneous code example:
+
+    fn main() {
+        return;
+        print("done");
+    }
+
+Remove the statement or move it before the jump:
+
+    fn main() {
+        print("done");
+        return;
+    }
+"#,
+        _ => return None,
+    };
+
+    Some(text)
+}
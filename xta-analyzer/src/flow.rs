@@ -0,0 +1,131 @@
+use xta_parser::ast::{Block, Expr, FunctionDeclStmt, Stmt};
+
+use crate::analyzer::{Analyzer, Error, Warning};
+
+// how control leaves a statement, ordered from weakest to strongest
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+enum Exit {
+    // execution may continue with the next statement
+    None,
+    // always leaves through `break` or `continue` (or a `return`)
+    Jump,
+    // always leaves the function through a `return`
+    Return,
+}
+
+// Control flow analysis, makes sure typed functions return on every path
+// and reports statements that can never be executed
+impl<'ctx> Analyzer<'ctx> {
+    pub(crate) fn check_flow(&mut self, func: &'ctx FunctionDeclStmt<'ctx>) {
+        let exit = self.block_exit(&func.body);
+
+        // a statement the parser failed on may have been the `return`, so it is not reported twice
+        if let Some(return_type) = func.return_type {
+            if exit != Exit::Return && !has_parse_error(&func.body) {
+                self.errors.push(Error::MissingReturn {
+                    loc: func.loc.clone(),
+                    span: func.span,
+                    name: func.name.to_string(),
+                    return_type: return_type.to_string(),
+                });
+            }
+        }
+    }
+
+    fn block_exit(&mut self, block: &'ctx Block<'ctx>) -> Exit {
+        let mut exit = Exit::None;
+
+        for stmt in block {
+            if exit != Exit::None {
+                self.warnings.push(Warning::UnreachableCode { loc: stmt.loc(), span: stmt.span() });
+                break;
+            }
+
+            exit = self.stmt_exit(stmt);
+        }
+
+        exit
+    }
+
+    fn stmt_exit(&mut self, stmt: &'ctx Stmt<'ctx>) -> Exit {
+        match stmt {
+            Stmt::Return(_) => Exit::Return,
+            Stmt::Break(_) | Stmt::Continue(_) => Exit::Jump,
+            Stmt::If(stmt) => {
+                // every branch is visited, so unreachable code is reported inside all of them
+                let mut exit = self.block_exit(&stmt.then);
+
+                for elif in &stmt.elif_branch {
+                    exit = exit.min(self.block_exit(&elif.then));
+                }
+
+                match &stmt.else_branch {
+                    Some(block) => exit.min(self.block_exit(block)),
+                    None => Exit::None,
+                }
+            }
+            // the loop may not run at all, and jumps only leave the loop
+            Stmt::While(stmt) => {
+                self.block_exit(&stmt.body);
+                Exit::None
+            }
+            Stmt::For(stmt) => {
+                self.block_exit(&stmt.body);
+                Exit::None
+            }
+            Stmt::Loop(stmt) => {
+                let body = self.block_exit(&stmt.body);
+
+                match &stmt.unless {
+                    // the body always runs once before the condition is checked, but a `break` or
+                    // `continue` can still reach the end of the loop without returning
+                    Some(_) if body == Exit::Return && !breaks(&stmt.body) && !continues(&stmt.body) => Exit::Return,
+                    Some(_) => Exit::None,
+                    // without a `break` the loop never finishes, so nothing after it runs
+                    None if !breaks(&stmt.body) => Exit::Return,
+                    None => Exit::None,
+                }
+            }
+            Stmt::VarDecl(_) | Stmt::Expr(_) | Stmt::FunctionDecl(_) => Exit::None,
+        }
+    }
+}
+
+// whether the block, or any block nested in it, holds a statement the parser failed on
+fn has_parse_error(block: &Block) -> bool {
+    block.iter().any(|stmt| match stmt {
+        Stmt::Expr(Expr::Error(_)) => true,
+        Stmt::If(stmt) => {
+            has_parse_error(&stmt.then)
+                || stmt.elif_branch.iter().any(|elif| has_parse_error(&elif.then))
+                || stmt.else_branch.as_ref().is_some_and(has_parse_error)
+        }
+        Stmt::While(stmt) => has_parse_error(&stmt.body),
+        Stmt::For(stmt) => has_parse_error(&stmt.body),
+        Stmt::Loop(stmt) => has_parse_error(&stmt.body),
+        _ => false,
+    })
+}
+
+// whether the block contains a `break` leaving the loop it belongs to
+fn breaks(block: &Block) -> bool {
+    jumps(block, |stmt| matches!(stmt, Stmt::Break(_)))
+}
+
+// whether the block contains a `continue` going to the condition of the loop it belongs to
+fn continues(block: &Block) -> bool {
+    jumps(block, |stmt| matches!(stmt, Stmt::Continue(_)))
+}
+
+fn jumps(block: &Block, is_jump: fn(&Stmt) -> bool) -> bool {
+    block.iter().any(|stmt| match stmt {
+        _ if is_jump(stmt) => true,
+        Stmt::If(stmt) => {
+            jumps(&stmt.then, is_jump)
+                || stmt.elif_branch.iter().any(|elif| jumps(&elif.then, is_jump))
+                || stmt.else_branch.as_ref().is_some_and(|block| jumps(block, is_jump))
+        }
+        // a jump inside a nested loop only affects that loop
+        _ => false,
+    })
+}
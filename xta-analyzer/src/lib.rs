@@ -1,2 +1,6 @@
 mod scope;
-pub mod analyzer;
\ No newline at end of file
+mod resolver;
+mod flow;
+pub mod analyzer;
+pub mod explain;
+pub mod types;
\ No newline at end of file
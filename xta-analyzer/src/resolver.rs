@@ -0,0 +1,348 @@
+use xta_lexer::token::{Loc, Span};
+use xta_parser::ast::{BinaryExpr, BinaryOpType, Block, CallExpr, Expr, FunctionDeclStmt, IdentifierExpr, Stmt, UnaryExpr};
+
+use crate::{
+    analyzer::{Analyzer, Error, BUILTINS},
+    scope::{Scope, Var, VarKind},
+    types::Type,
+};
+
+// Name resolution and type checking, binds every identifier to a declaration
+// in the scope stack and infers the type of every expression
+impl<'ctx> Analyzer<'ctx> {
+    pub(crate) fn resolve_function(&mut self, func: &'ctx FunctionDeclStmt<'ctx>) {
+        let mut params = Scope::new();
+        for param in &func.params {
+            let ty = self.resolve_type(param.param_type, &param.loc, param.span);
+
+            if let Some(previous) = params.get_var(param.name) {
+                self.errors.push(Error::DuplicateParam {
+                    loc: param.loc.clone(),
+                    span: param.span,
+                    name: param.name.to_string(),
+                    previous: previous.name.loc.clone(),
+                    previous_span: previous.name.span,
+                });
+                continue;
+            }
+
+            params.insert_var(IdentifierExpr { name: param.name, loc: param.loc.clone(), span: param.span }, ty, VarKind::Let);
+        }
+
+        self.return_type = match func.return_type {
+            Some(name) => self.resolve_type(name, &func.loc, func.span),
+            None => Type::None,
+        };
+
+        self.scopes.push(params);
+        self.resolve_block(&func.body);
+        self.scopes.pop();
+    }
+
+    // the declared return type of a function, without reporting invalid type names again
+    fn function_return_type(func: &FunctionDeclStmt) -> Type {
+        match func.return_type {
+            Some(name) => Type::from_name(name).unwrap_or(Type::Unknown),
+            None => Type::None,
+        }
+    }
+
+    fn resolve_type(&mut self, name: &str, loc: &Loc, span: Span) -> Type {
+        Type::from_name(name).unwrap_or_else(|| {
+            self.errors.push(Error::UnknownType { loc: loc.clone(), span, name: name.to_string() });
+            Type::Unknown
+        })
+    }
+
+    fn resolve_block(&mut self, block: &'ctx Block<'ctx>) {
+        self.push_block_scope(block);
+        for stmt in block {
+            self.resolve_stmt(stmt);
+        }
+        self.scopes.pop();
+    }
+
+    // opens a scope for the given statements, aware of every variable they declare
+    pub(crate) fn push_block_scope(&mut self, stmts: &'ctx [Stmt<'ctx>]) {
+        let mut scope = Scope::new();
+        for stmt in stmts {
+            if let Stmt::VarDecl(decl) = stmt {
+                scope.insert_upcoming(decl.name, decl.loc.clone(), decl.span);
+            }
+        }
+
+        self.scopes.push(scope);
+    }
+
+    pub(crate) fn resolve_stmt(&mut self, stmt: &'ctx Stmt<'ctx>) {
+        match stmt {
+            Stmt::VarDecl(decl) => {
+                // the initializer is resolved first, so `let a = a;` refers to an outer `a`
+                let inferred = match &decl.value {
+                    Some(value) => self.resolve_expr(value),
+                    None => Type::Unknown,
+                };
+
+                let ty = match decl.var_type {
+                    Some(name) => {
+                        let annotated = self.resolve_type(name, &decl.loc, decl.span);
+                        if !annotated.accepts(inferred) {
+                            self.errors.push(Error::AnnotationMismatch {
+                                loc: decl.loc.clone(),
+                                span: decl.span,
+                                name: decl.name.to_string(),
+                                annotated,
+                                inferred,
+                            });
+                        }
+                        annotated
+                    }
+                    None => inferred,
+                };
+
+                if inferred == Type::None {
+                    self.errors.push(Error::NoneBinding { loc: decl.loc.clone(), span: decl.span, name: decl.name.to_string() });
+                }
+
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.insert_var(IdentifierExpr { name: decl.name, loc: decl.loc.clone(), span: decl.span }, ty, if decl.is_const { VarKind::Const } else { VarKind::Let });
+                }
+            }
+            Stmt::If(stmt) => {
+                self.resolve_condition(&stmt.condition);
+                self.resolve_block(&stmt.then);
+
+                for elif in &stmt.elif_branch {
+                    self.resolve_condition(&elif.condition);
+                    self.resolve_block(&elif.then);
+                }
+
+                if let Some(block) = &stmt.else_branch {
+                    self.resolve_block(block);
+                }
+            }
+            Stmt::Return(stmt) => {
+                let found = match &stmt.value {
+                    Some(value) => self.resolve_expr(value),
+                    None => Type::None,
+                };
+
+                if !self.return_type.accepts(found) {
+                    self.errors.push(Error::ReturnTypeMismatch { loc: stmt.loc.clone(), span: stmt.span, expected: self.return_type, found });
+                }
+            }
+            Stmt::While(stmt) => {
+                self.resolve_condition(&stmt.condition);
+                self.resolve_loop_body(&stmt.body);
+            }
+            Stmt::For(stmt) => {
+                self.resolve_bound(&stmt.start);
+                self.resolve_bound(&stmt.end);
+
+                // the loop variable lives in its own scope around the body
+                let mut scope = Scope::new();
+                scope.insert_var(stmt.var.clone(), Type::Int, VarKind::Loop);
+
+                self.scopes.push(scope);
+                self.resolve_loop_body(&stmt.body);
+                self.scopes.pop();
+            }
+            Stmt::Loop(stmt) => {
+                self.resolve_loop_body(&stmt.body);
+
+                // the condition is checked after the body's scope is closed
+                if let Some(condition) = &stmt.unless {
+                    self.resolve_condition(condition);
+                }
+            }
+            Stmt::Break(stmt) => {
+                if self.loop_depth == 0 {
+                    self.errors.push(Error::OutsideLoop { loc: stmt.loc.clone(), span: stmt.span, keyword: "break" });
+                }
+            }
+            Stmt::Continue(stmt) => {
+                if self.loop_depth == 0 {
+                    self.errors.push(Error::OutsideLoop { loc: stmt.loc.clone(), span: stmt.span, keyword: "continue" });
+                }
+            }
+            Stmt::Expr(expr) => {
+                self.resolve_expr(expr);
+            }
+            Stmt::FunctionDecl(func) => {
+                self.errors.push(Error::NestedFunction { loc: func.loc.clone(), span: func.span });
+            }
+        }
+    }
+
+    fn resolve_loop_body(&mut self, body: &'ctx Block<'ctx>) {
+        self.loop_depth += 1;
+        self.resolve_block(body);
+        self.loop_depth -= 1;
+    }
+
+    fn resolve_bound(&mut self, expr: &'ctx Expr<'ctx>) {
+        let ty = self.resolve_expr(expr);
+        if !Type::Int.accepts(ty) {
+            self.errors.push(Error::TypeMismatch { loc: expr.loc(), span: expr.span(), expected: Type::Int, found: ty });
+        }
+    }
+
+    fn resolve_condition(&mut self, expr: &'ctx Expr<'ctx>) {
+        let ty = self.resolve_expr(expr);
+        if !Type::Bool.accepts(ty) {
+            self.errors.push(Error::TypeMismatch { loc: expr.loc(), span: expr.span(), expected: Type::Bool, found: ty });
+        }
+    }
+
+    fn resolve_expr(&mut self, expr: &'ctx Expr<'ctx>) -> Type {
+        match expr {
+            Expr::Identifier(id) => self.resolve_identifier(id),
+            Expr::Literal(literal) => Type::of_literal(&literal.value),
+            Expr::Call(call) => self.resolve_call(call),
+            Expr::Binary(expr) => self.resolve_binary(expr),
+            Expr::Unary(expr) => self.resolve_unary(expr),
+            // already reported by the parser
+            Expr::Error(_) => Type::Unknown,
+        }
+    }
+
+    fn resolve_call(&mut self, call: &'ctx CallExpr<'ctx>) -> Type {
+        let args: Vec<Type> = call.args.iter().map(|arg| self.resolve_expr(arg)).collect();
+
+        if let Some(func) = self.functions.get(call.name).copied() {
+            self.check_arguments(call, func, &args);
+            return Self::function_return_type(func);
+        }
+
+        if BUILTINS.contains(&call.name) {
+            return Type::None;
+        }
+
+        self.errors.push(Error::UndefinedFunction { loc: call.loc.clone(), span: call.span, name: call.name.to_string() });
+        Type::Unknown
+    }
+
+    // compares the arguments of a call against the parameters of the called function
+    fn check_arguments(&mut self, call: &CallExpr<'ctx>, func: &FunctionDeclStmt<'ctx>, args: &[Type]) {
+        if func.params.len() != args.len() {
+            self.errors.push(Error::ArgumentCount {
+                loc: call.loc.clone(),
+                span: call.span,
+                name: call.name.to_string(),
+                expected: func.params.len(),
+                found: args.len(),
+                decl: func.loc.clone(),
+                decl_span: func.name_span,
+            });
+            return;
+        }
+
+        for ((param, arg), found) in func.params.iter().zip(&call.args).zip(args) {
+            let expected = Type::from_name(param.param_type).unwrap_or(Type::Unknown);
+            if !expected.accepts(*found) {
+                self.errors.push(Error::ArgumentTypeMismatch {
+                    loc: arg.loc(),
+                    span: arg.span(),
+                    name: call.name.to_string(),
+                    param: param.name.to_string(),
+                    expected,
+                    found: *found,
+                    decl: param.loc.clone(),
+                    decl_span: param.span,
+                });
+            }
+        }
+    }
+
+    fn resolve_binary(&mut self, expr: &'ctx BinaryExpr<'ctx>) -> Type {
+        let left = self.resolve_expr(&expr.left);
+        let right = self.resolve_expr(&expr.right);
+
+        if expr.operator.is_assignment() {
+            self.check_assignable(&expr.left);
+        }
+
+        Type::binary(&expr.operator, left, right).unwrap_or_else(|| {
+            // a plain assignment only fails when the value does not fit the variable
+            let error = match expr.operator {
+                BinaryOpType::Assign => Error::TypeMismatch { loc: expr.right.loc(), span: expr.right.span(), expected: left, found: right },
+                _ => Error::InvalidOperands {
+                    loc: expr.loc.clone(),
+                    span: expr.span,
+                    op_span: expr.op_span,
+                    operator: expr.operator.to_string(),
+                    left,
+                    right,
+                },
+            };
+            self.errors.push(error);
+            Type::Unknown
+        })
+    }
+
+    fn resolve_unary(&mut self, expr: &'ctx UnaryExpr<'ctx>) -> Type {
+        let operand = self.resolve_expr(&expr.operand);
+
+        if expr.operator.is_update() {
+            self.check_assignable(&expr.operand);
+        }
+
+        Type::unary(&expr.operator, operand).unwrap_or_else(|| {
+            self.errors.push(Error::InvalidOperand {
+                loc: expr.loc.clone(),
+                span: expr.span,
+                operator: expr.operator.to_string(),
+                found: operand,
+            });
+            Type::Unknown
+        })
+    }
+
+    // only variables declared with `let` can be assigned to or incremented
+    fn check_assignable(&mut self, target: &Expr<'ctx>) {
+        let id = match target {
+            Expr::Identifier(id) => id,
+            // already reported by the parser
+            Expr::Error(_) => return,
+            _ => {
+                self.errors.push(Error::InvalidAssignTarget { loc: target.loc(), span: target.span() });
+                return;
+            }
+        };
+
+        let Some(var) = self.lookup_var(id.name) else {
+            return;
+        };
+
+        let (name, decl, decl_span) = (id.name.to_string(), var.name.loc.clone(), var.name.span);
+        match var.kind {
+            VarKind::Let => {}
+            VarKind::Const => self.errors.push(Error::AssignToConst { loc: id.loc.clone(), span: id.span, name, decl, decl_span }),
+            VarKind::Loop => self.errors.push(Error::AssignToLoopVar { loc: id.loc.clone(), span: id.span, name, decl, decl_span }),
+        }
+    }
+
+    fn resolve_identifier(&mut self, id: &IdentifierExpr<'ctx>) -> Type {
+        if let Some(var) = self.lookup_var(id.name) {
+            return var.ty;
+        }
+
+        let upcoming = self.scopes.iter().rev().find_map(|scope| scope.get_upcoming(id.name));
+        match upcoming {
+            Some((decl, decl_span)) => self.errors.push(Error::UseBeforeDeclaration {
+                loc: id.loc.clone(),
+                span: id.span,
+                name: id.name.to_string(),
+                decl: decl.clone(),
+                decl_span: *decl_span,
+            }),
+            None => self.errors.push(Error::UndeclaredIdentifier { loc: id.loc.clone(), span: id.span, name: id.name.to_string() }),
+        }
+
+        Type::Unknown
+    }
+
+    fn lookup_var(&self, name: &str) -> Option<&Var<'ctx>> {
+        self.scopes.iter().rev().find_map(|scope| scope.get_var(name))
+    }
+}
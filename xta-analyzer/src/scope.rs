@@ -1,27 +1,53 @@
 use std::collections::HashMap;
 
+use xta_lexer::token::{Loc, Span};
 use xta_parser::ast::IdentifierExpr;
 
-struct Var<'ctx> {
-    name: IdentifierExpr<'ctx>,
+use crate::types::Type;
+
+pub(crate) struct Var<'ctx> {
+    pub name: IdentifierExpr<'ctx>,
+    pub ty: Type,
+    pub kind: VarKind,
+}
+
+// how a variable was declared, which decides whether it can be assigned to
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum VarKind {
+    Let,
+    Const,
+    // the counter of a `for` loop
+    Loop,
 }
 
-struct Scope<'ctx> {
+pub(crate) struct Scope<'ctx> {
     vars: HashMap<&'ctx str, Var<'ctx>>,
+    // variables declared further down in this scope, used to detect use-before-declaration
+    upcoming: HashMap<&'ctx str, (Loc, Span)>,
 }
 
 impl<'ctx> Scope<'ctx> {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             vars: HashMap::new(),
+            upcoming: HashMap::new(),
         }
     }
 
-    fn insert_var(&mut self, name: IdentifierExpr<'ctx>) {
-        self.vars.insert(name.name, Var { name });
+    pub fn insert_var(&mut self, name: IdentifierExpr<'ctx>, ty: Type, kind: VarKind) {
+        self.vars.insert(name.name, Var { name, ty, kind });
     }
 
-    fn get_var(&self, name: &str) -> Option<&Var<'ctx>> {
+    pub fn get_var(&self, name: &str) -> Option<&Var<'ctx>> {
         self.vars.get(name)
     }
-}
\ No newline at end of file
+
+    // marks a variable as declared later on in this scope, the first declaration wins
+    pub fn insert_upcoming(&mut self, name: &'ctx str, loc: Loc, span: Span) {
+        self.upcoming.entry(name).or_insert((loc, span));
+    }
+
+    pub fn get_upcoming(&self, name: &str) -> Option<&(Loc, Span)> {
+        self.upcoming.get(name)
+    }
+}
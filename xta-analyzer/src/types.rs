@@ -0,0 +1,100 @@
+use xta_parser::ast::{BinaryOpType, Literal, UnaryOpType};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Type {
+    Int,
+    Double,
+    String,
+    Bool,
+    None,
+
+    // the type of an expression that already failed to check, never reported again
+    Unknown,
+}
+
+impl Type {
+    // resolves a type name written in the source (e.g. `fn foo(a Int)`)
+    pub fn from_name(name: &str) -> Option<Type> {
+        match name {
+            "Int" => Some(Type::Int),
+            "Double" => Some(Type::Double),
+            "String" => Some(Type::String),
+            "Bool" => Some(Type::Bool),
+            _ => None,
+        }
+    }
+
+    pub fn of_literal(literal: &Literal) -> Type {
+        match literal {
+            Literal::Integer(_) => Type::Int,
+            Literal::Double(_) => Type::Double,
+            Literal::String(_) => Type::String,
+            Literal::Boolean(_) => Type::Bool,
+            Literal::None => Type::None,
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Type::Int | Type::Double)
+    }
+
+    // whether a value of type `other` can be used where `self` is expected
+    pub fn accepts(&self, other: Type) -> bool {
+        *self == other || *self == Type::Unknown || other == Type::Unknown
+    }
+
+    // the type of `left <op> right`, or None when the operands are invalid for the operator
+    pub fn binary(operator: &BinaryOpType, left: Type, right: Type) -> Option<Type> {
+        if left == Type::Unknown || right == Type::Unknown {
+            return Some(Type::Unknown);
+        }
+
+        // `a += b` is only valid when `a + b` can be stored back into `a`
+        if let Some(base) = operator.compound_base() {
+            return Type::binary(&base, left, right).filter(|result| left.accepts(*result)).map(|_| left);
+        }
+
+        match operator {
+            _ if operator.is_logical() => (left == Type::Bool && right == Type::Bool).then_some(Type::Bool),
+            _ if operator.is_bitwise() => (left == Type::Int && right == Type::Int).then_some(Type::Int),
+            BinaryOpType::Eq | BinaryOpType::Neq => {
+                (left == right || (left.is_numeric() && right.is_numeric())).then_some(Type::Bool)
+            }
+            _ if operator.is_comparison() => {
+                ((left.is_numeric() && right.is_numeric()) || (left == Type::String && right == Type::String)).then_some(Type::Bool)
+            }
+            BinaryOpType::Assign => left.accepts(right).then_some(left),
+            BinaryOpType::Add if left == Type::String && right == Type::String => Some(Type::String),
+            _ => match (left, right) {
+                (Type::Int, Type::Int) => Some(Type::Int),
+                (left, right) if left.is_numeric() && right.is_numeric() => Some(Type::Double),
+                _ => None,
+            },
+        }
+    }
+
+    // the type of `<op> operand`, or None when the operand is invalid for the operator
+    pub fn unary(operator: &UnaryOpType, operand: Type) -> Option<Type> {
+        match (operator, operand) {
+            (_, Type::Unknown) => Some(Type::Unknown),
+            (UnaryOpType::Neg, ty) if ty.is_numeric() => Some(ty),
+            (operator, ty) if operator.is_update() && ty.is_numeric() => Some(ty),
+            (UnaryOpType::Not, Type::Bool) => Some(Type::Bool),
+            (UnaryOpType::BitNot, Type::Int) => Some(Type::Int),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Type::Int => write!(f, "Int"),
+            Type::Double => write!(f, "Double"),
+            Type::String => write!(f, "String"),
+            Type::Bool => write!(f, "Bool"),
+            Type::None => write!(f, "None"),
+            Type::Unknown => write!(f, "{{unknown}}"),
+        }
+    }
+}
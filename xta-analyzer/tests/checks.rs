@@ -0,0 +1,208 @@
+use xta_analyzer::{analyzer::{Analyzer, Error}, types::Type};
+use xta_lexer::{scanner::Scanner, token::Span};
+use xta_parser::parser::Parser;
+
+fn analyze(source: &str) -> Vec<Error> {
+    let mut parser = Parser::new(Scanner::new(source));
+    let stmts = parser.parse_file();
+    assert!(parser.errors.is_empty(), "unexpected parse errors: {:?}", parser.errors);
+
+    Analyzer::new(&stmts).errors
+}
+
+#[test]
+fn valid_program_has_no_errors() {
+    let errors = analyze(
+        "const limit = 3;
+         fn add(a Int, b Int) -> Int { return a + b; }
+         fn main() {
+             let total Int = 0;
+             for i in 0..limit { total += add(i, 1); }
+             while total > 0 { total--; if total == 2 { break; } }
+             print(total);
+         }",
+    );
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+}
+
+#[test]
+fn top_level_declarations() {
+    let errors = analyze("let limit = 10; fn main() {}");
+    assert!(matches!(errors.as_slice(), [Error::UnexpectedNonFunction { .. }]), "found {:?}", errors);
+
+    let errors = analyze("fn run() {} fn run() {} fn main() {}");
+    assert!(matches!(errors.as_slice(), [Error::DuplicateFunction { name, .. }] if name == "run"), "found {:?}", errors);
+
+    let errors = analyze("fn add(a Int, a Int) -> Int { return a + a; } fn main() {}");
+    assert!(matches!(errors.as_slice(), [Error::DuplicateParam { name, .. }] if name == "a"), "found {:?}", errors);
+
+    let errors = analyze("fn main() { fn helper() {} }");
+    assert!(matches!(errors.as_slice(), [Error::NestedFunction { .. }]), "found {:?}", errors);
+}
+
+#[test]
+fn main_function() {
+    let errors = analyze("fn start() {}");
+    assert!(matches!(errors.as_slice(), [Error::MissingMain]), "found {:?}", errors);
+
+    let errors = analyze("fn main(a Int) {}");
+    assert!(matches!(errors.as_slice(), [Error::MainWithParams { .. }]), "found {:?}", errors);
+}
+
+#[test]
+fn undeclared_names() {
+    let errors = analyze("fn main() { print(count); }");
+    assert!(matches!(errors.as_slice(), [Error::UndeclaredIdentifier { name, .. }] if name == "count"), "found {:?}", errors);
+
+    let errors = analyze("fn main() { print(count); let count = 0; }");
+    assert!(matches!(errors.as_slice(), [Error::UseBeforeDeclaration { name, .. }] if name == "count"), "found {:?}", errors);
+
+    let errors = analyze("fn main() { greet(); }");
+    assert!(matches!(errors.as_slice(), [Error::UndefinedFunction { name, .. }] if name == "greet"), "found {:?}", errors);
+
+    // a block's variables end with the block
+    let errors = analyze("fn main() { if true { let a = 1; } print(a); }");
+    assert!(matches!(errors.as_slice(), [Error::UndeclaredIdentifier { name, .. }] if name == "a"), "found {:?}", errors);
+}
+
+#[test]
+fn assignments() {
+    let errors = analyze("fn main() { const limit = 10; limit = 20; }");
+    assert!(matches!(errors.as_slice(), [Error::AssignToConst { name, .. }] if name == "limit"), "found {:?}", errors);
+
+    let errors = analyze("const limit = 10; fn main() { limit += 1; }");
+    assert!(matches!(errors.as_slice(), [Error::AssignToConst { name, .. }] if name == "limit"), "found {:?}", errors);
+
+    let errors = analyze("fn main() { for i in 0..3 { i += 1; } }");
+    assert!(matches!(errors.as_slice(), [Error::AssignToLoopVar { name, .. }] if name == "i"), "found {:?}", errors);
+
+    let errors = analyze("fn main() { let a = 1; (a + 1)++; }");
+    assert!(matches!(errors.as_slice(), [Error::InvalidAssignTarget { .. }]), "found {:?}", errors);
+}
+
+#[test]
+fn break_and_continue_outside_a_loop() {
+    let errors = analyze("fn main() { break; }");
+    assert!(matches!(errors.as_slice(), [Error::OutsideLoop { keyword: "break", .. }]), "found {:?}", errors);
+
+    let errors = analyze("fn main() { if true { continue; } }");
+    assert!(matches!(errors.as_slice(), [Error::OutsideLoop { keyword: "continue", .. }]), "found {:?}", errors);
+
+    let errors = analyze("fn main() { while true { if true { break; } continue; } }");
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+}
+
+#[test]
+fn missing_return() {
+    let errors = analyze("fn sign(a Int) -> Int { if a < 0 { return 0 - 1; } } fn main() {}");
+    assert!(
+        matches!(errors.as_slice(), [Error::MissingReturn { name, return_type, .. }] if name == "sign" && return_type == "Int"),
+        "found {:?}",
+        errors
+    );
+}
+
+#[test]
+fn type_mismatches() {
+    let errors = analyze("fn main() { let a = 1; if a { print(a); } }");
+    assert!(
+        matches!(errors.as_slice(), [Error::TypeMismatch { expected: Type::Bool, found: Type::Int, .. }]),
+        "found {:?}",
+        errors
+    );
+
+    let errors = analyze("fn name() -> String { return 1; } fn main() {}");
+    assert!(
+        matches!(errors.as_slice(), [Error::ReturnTypeMismatch { expected: Type::String, found: Type::Int, .. }]),
+        "found {:?}",
+        errors
+    );
+
+    let errors = analyze("fn main() { let a Int = \"one\"; }");
+    assert!(
+        matches!(errors.as_slice(), [Error::AnnotationMismatch { annotated: Type::Int, inferred: Type::String, .. }]),
+        "found {:?}",
+        errors
+    );
+
+    let source = "fn main() { let a = 5; a = \"s\"; }";
+    let errors = analyze(source);
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [Error::TypeMismatch { expected: Type::Int, found: Type::String, span, .. }] if &source[span.start..span.end] == "\"s\""
+        ),
+        "found {:?}",
+        errors
+    );
+
+    // compound assignments still check their operator
+    let errors = analyze("fn main() { let a = 5; a += \"s\"; }");
+    assert!(matches!(errors.as_slice(), [Error::InvalidOperands { .. }]), "found {:?}", errors);
+
+    let errors = analyze("fn double(a Integer) -> Int { return 2; } fn main() {}");
+    assert!(matches!(errors.as_slice(), [Error::UnknownType { name, .. }] if name == "Integer"), "found {:?}", errors);
+
+    let errors = analyze("fn log() { print(\"log\"); } fn main() { let a = log(); }");
+    assert!(matches!(errors.as_slice(), [Error::NoneBinding { name, .. }] if name == "a"), "found {:?}", errors);
+}
+
+#[test]
+fn operators() {
+    let errors = analyze("fn main() { print(1 + \"2\"); }");
+    assert!(
+        matches!(errors.as_slice(), [Error::InvalidOperands { operator, left: Type::Int, right: Type::String, .. }] if operator == "+"),
+        "found {:?}",
+        errors
+    );
+
+    let errors = analyze("fn main() { print(-\"one\"); }");
+    assert!(
+        matches!(errors.as_slice(), [Error::InvalidOperand { operator, found: Type::String, .. }] if operator == "-"),
+        "found {:?}",
+        errors
+    );
+
+    // a failed operand is not reported again by the expression around it
+    let errors = analyze("fn main() { print((1 + \"2\") * 3); }");
+    assert!(matches!(errors.as_slice(), [Error::InvalidOperands { .. }]), "found {:?}", errors);
+}
+
+#[test]
+fn call_arguments() {
+    let errors = analyze("fn add(a Int, b Int) -> Int { return a + b; } fn main() { print(add(1)); }");
+    assert!(
+        matches!(errors.as_slice(), [Error::ArgumentCount { name, expected: 2, found: 1, .. }] if name == "add"),
+        "found {:?}",
+        errors
+    );
+
+    let errors = analyze("fn square(a Int) -> Int { return a * a; } fn main() { print(square(\"2\")); }");
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [Error::ArgumentTypeMismatch { name, param, expected: Type::Int, found: Type::String, .. }] if name == "square" && param == "a"
+        ),
+        "found {:?}",
+        errors
+    );
+}
+
+#[test]
+fn declaration_notes_point_at_the_function_name() {
+    let source = "fn add(a Int) -> Int { return a; } fn add() {} fn main() { print(add()); }";
+    let first = Span::new(3, 6);
+    let second = Span::new(38, 41);
+    assert_eq!(&source[second.start..second.end], "add");
+
+    let errors = analyze(source);
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [Error::DuplicateFunction { span, previous_span, .. }, Error::ArgumentCount { decl_span, .. }]
+                if *span == second && *previous_span == first && *decl_span == first
+        ),
+        "found {:?}",
+        errors
+    );
+}
@@ -0,0 +1,75 @@
+use xta_analyzer::analyzer::{Analyzer, Error, Warning};
+use xta_lexer::scanner::Scanner;
+use xta_parser::parser::Parser;
+
+fn analyze(source: &str) -> (Vec<Error>, Vec<Warning>) {
+    let mut parser = Parser::new(Scanner::new(source));
+    let stmts = parser.parse_file();
+    assert!(parser.errors.is_empty(), "unexpected parse errors: {:?}", parser.errors);
+
+    let analyzer = Analyzer::new(&stmts);
+    (analyzer.errors, analyzer.warnings)
+}
+
+// analyzes a file the parser had to recover from
+fn analyze_broken(source: &str) -> Vec<Error> {
+    let mut parser = Parser::new(Scanner::new(source));
+    let stmts = parser.parse_file();
+    assert!(!parser.errors.is_empty(), "expected parse errors");
+
+    Analyzer::new(&stmts).errors
+}
+
+fn missing_return(source: &str) -> bool {
+    let (errors, _) = analyze(source);
+    errors.iter().any(|error| matches!(error, Error::MissingReturn { .. }))
+}
+
+#[test]
+fn if_without_else_may_fall_through() {
+    assert!(missing_return("fn f(a Int) -> Int { if a > 0 { return 1; } } fn main() {}"));
+    assert!(!missing_return("fn f(a Int) -> Int { if a > 0 { return 1; } else { return 2; } } fn main() {}"));
+}
+
+#[test]
+fn loop_without_break_never_finishes() {
+    assert!(!missing_return("fn f() -> Int { loop { } } fn main() {}"));
+    assert!(missing_return("fn f() -> Int { loop { break; } } fn main() {}"));
+}
+
+#[test]
+fn loop_unless_returning_body_always_returns() {
+    assert!(!missing_return("fn f() -> Int { loop { return 1; } unless true; } fn main() {}"));
+}
+
+#[test]
+fn loop_unless_continue_reaches_the_condition() {
+    assert!(missing_return("fn f() -> Int { loop { if true { continue; } return 1; } unless true; } fn main() {}"));
+}
+
+#[test]
+fn loop_unless_break_leaves_without_returning() {
+    assert!(missing_return("fn f() -> Int { loop { if true { break; } return 1; } unless true; } fn main() {}"));
+}
+
+#[test]
+fn nested_loop_jumps_do_not_count() {
+    assert!(!missing_return("fn f() -> Int { loop { while true { continue; } return 1; } unless true; } fn main() {}"));
+}
+
+#[test]
+fn statement_after_return_is_unreachable() {
+    let (errors, warnings) = analyze("fn main() { return; print(1); }");
+
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+    assert!(matches!(warnings.as_slice(), [Warning::UnreachableCode { .. }]), "unexpected warnings: {:?}", warnings);
+}
+
+#[test]
+fn broken_statement_may_have_returned() {
+    let errors = analyze_broken("fn foo() -> Int { return a + } fn main() {}");
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+
+    let errors = analyze_broken("fn foo(a Int) -> Int { if a > 0 { return (a; } else { return 0; } } fn main() {}");
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+}
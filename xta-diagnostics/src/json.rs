@@ -0,0 +1,57 @@
+use serde_json::{json, Value};
+use xta_lexer::{line_index::LineIndex, token::Span};
+
+use crate::diagnostic::Diagnostic;
+
+// Serializes a diagnostic for tools, e.g.
+// {"severity":"error","code":null,"message":"...","file":"main.xta","span":{...},"labels":[...],"suggestions":[...]}
+pub fn to_json(diagnostic: &Diagnostic, path: &str, index: &LineIndex) -> Value {
+    let labels: Vec<Value> = diagnostic
+        .labels
+        .iter()
+        .map(|label| {
+            json!({
+                "span": span_json(label.span, index),
+                "message": label.message,
+                "primary": label.primary,
+            })
+        })
+        .collect();
+
+    let suggestions: Vec<Value> = diagnostic
+        .suggestions
+        .iter()
+        .map(|suggestion| {
+            json!({
+                "span": span_json(suggestion.span, index),
+                "replacement": suggestion.replacement,
+                "message": suggestion.message,
+            })
+        })
+        .collect();
+
+    json!({
+        "severity": diagnostic.severity.to_string(),
+        "code": diagnostic.code,
+        "message": diagnostic.message,
+        "file": path,
+        "span": diagnostic.primary_span().map(|span| span_json(span, index)),
+        "labels": labels,
+        "notes": diagnostic.notes,
+        "suggestions": suggestions,
+    })
+}
+
+// byte offsets together with 1-based lines and columns
+fn span_json(span: Span, index: &LineIndex) -> Value {
+    let (start, end) = index.span_range(span);
+
+    json!({
+        "start": span.start,
+        "end": span.end,
+        "line_start": start.line,
+        "column_start": start.col,
+        "line_end": end.line,
+        "column_end": end.col,
+    })
+}
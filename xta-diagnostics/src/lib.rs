@@ -0,0 +1,3 @@
+pub mod diagnostic;
+pub mod json;
+pub mod renderer;
\ No newline at end of file
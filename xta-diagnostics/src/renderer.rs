@@ -0,0 +1,126 @@
+use xta_lexer::line_index::LineIndex;
+
+use crate::diagnostic::{Diagnostic, Label, Severity};
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+// Renders diagnostics together with the source lines they point at:
+//
+// error: Expected `;`, found `}`
+//  --> main.xta:3:14
+//   |
+// 3 |     let a = 5
+//   |              ^ expected `;`
+pub struct Renderer {
+    color: bool,
+}
+
+impl Renderer {
+    pub fn new(color: bool) -> Self {
+        Self { color }
+    }
+
+    pub fn render(&self, diagnostic: &Diagnostic, path: &str, index: &LineIndex) -> String {
+        let mut out = String::new();
+
+        let severity = match diagnostic.code {
+            Some(code) => format!("{}[{}]", diagnostic.severity, code),
+            None => diagnostic.severity.to_string(),
+        };
+        let header = match diagnostic.severity {
+            Severity::Error => self.paint(RED, &severity),
+            Severity::Warning => self.paint(YELLOW, &severity),
+        };
+        out.push_str(&format!("{}{}\n", header, self.paint(BOLD, &format!(": {}", diagnostic.message))));
+
+        let mut labels: Vec<&Label> = diagnostic.labels.iter().collect();
+        labels.sort_by_key(|label| (label.span.start, !label.primary));
+
+        // the gutter is as wide as the largest line number that gets printed
+        let width = labels
+            .iter()
+            .map(|label| index.line_col(label.span.start).line.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let gutter = self.paint(BLUE, &format!("{:width$} |", ""));
+
+        match diagnostic.primary_span() {
+            Some(span) => {
+                let at = index.line_col(span.start);
+                out.push_str(&format!("{:width$}{} {}:{}\n", "", self.paint(BLUE, "-->"), path, at));
+            }
+            None => out.push_str(&format!("{} {}\n", self.paint(BLUE, "-->"), path)),
+        }
+
+        if !labels.is_empty() {
+            out.push_str(&format!("{}\n", gutter));
+        }
+
+        let mut last_line = None;
+        for label in labels {
+            let (start, end) = index.span_range(label.span);
+            let text = index.line_text(start.line);
+
+            if last_line != Some(start.line) {
+                // lines between two labels are left out
+                if last_line.is_some_and(|line| start.line > line + 1) {
+                    out.push_str(&format!("{}\n", self.paint(BLUE, "...")));
+                }
+
+                let number = self.paint(BLUE, &format!("{:>width$} |", start.line));
+                out.push_str(&format!("{} {}\n", number, text));
+                last_line = Some(start.line);
+            }
+
+            // spans running over several lines are underlined up to the end of their first line
+            let line_len = text.chars().count();
+            let end_col = if end.line == start.line { end.col } else { line_len + 1 };
+            let len = end_col.saturating_sub(start.col).max(1);
+
+            // tabs are kept so the markers line up with the source line above
+            let padding: String = text
+                .chars()
+                .take(start.col - 1)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+
+            let (marker, style) = match (label.primary, diagnostic.severity) {
+                (true, Severity::Error) => ('^', RED),
+                (true, Severity::Warning) => ('^', YELLOW),
+                (false, _) => ('-', BLUE),
+            };
+            let mut underline: String = std::iter::repeat_n(marker, len).collect();
+            if !label.message.is_empty() {
+                underline.push(' ');
+                underline.push_str(&label.message);
+            }
+
+            out.push_str(&format!("{} {}{}\n", gutter, padding, self.paint(style, &underline)));
+        }
+
+        for note in &diagnostic.notes {
+            out.push_str(&format!("{:width$} {} {}\n", "", self.paint(BLUE, "="), self.paint(BOLD, "note:") + " " + note));
+        }
+        for suggestion in &diagnostic.suggestions {
+            let help = match suggestion.replacement.is_empty() {
+                true => suggestion.message.clone(),
+                false => format!("{}: `{}`", suggestion.message, suggestion.replacement),
+            };
+            out.push_str(&format!("{:width$} {} {}\n", "", self.paint(BLUE, "="), self.paint(BOLD, "help:") + " " + &help));
+        }
+
+        out
+    }
+
+    fn paint(&self, style: &str, text: &str) -> String {
+        if self.color {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
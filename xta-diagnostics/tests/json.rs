@@ -0,0 +1,70 @@
+use serde_json::json;
+use xta_diagnostics::{diagnostic::Diagnostic, json::to_json};
+use xta_lexer::{line_index::LineIndex, token::Span};
+
+#[test]
+fn diagnostic_serializes_every_field() {
+    let source = "const limit = 10;\nfn main() {\n    limit = 20;\n}\n";
+    let diagnostic = Diagnostic::error("Cannot assign to constant `limit`")
+        .with_code("XA0009")
+        .with_label(Span::new(34, 39), "assigned here")
+        .with_secondary(Span::new(0, 17), "declared here")
+        .with_note("constants cannot change after they are declared")
+        .with_suggestion(Span::new(0, 5), "let", "declare it with");
+
+    assert_eq!(
+        to_json(&diagnostic, "main.xta", &LineIndex::new(source)),
+        json!({
+            "severity": "error",
+            "code": "XA0009",
+            "message": "Cannot assign to constant `limit`",
+            "file": "main.xta",
+            "span": {"start": 34, "end": 39, "line_start": 3, "column_start": 5, "line_end": 3, "column_end": 10},
+            "labels": [
+                {
+                    "span": {"start": 34, "end": 39, "line_start": 3, "column_start": 5, "line_end": 3, "column_end": 10},
+                    "message": "assigned here",
+                    "primary": true,
+                },
+                {
+                    "span": {"start": 0, "end": 17, "line_start": 1, "column_start": 1, "line_end": 1, "column_end": 18},
+                    "message": "declared here",
+                    "primary": false,
+                },
+            ],
+            "notes": ["constants cannot change after they are declared"],
+            "suggestions": [
+                {
+                    "span": {"start": 0, "end": 5, "line_start": 1, "column_start": 1, "line_end": 1, "column_end": 6},
+                    "replacement": "let",
+                    "message": "declare it with",
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn diagnostic_without_labels_has_a_null_span() {
+    let diagnostic = Diagnostic::warning("Unused variable `x`");
+    let value = to_json(&diagnostic, "main.xta", &LineIndex::new(""));
+
+    assert_eq!(value["severity"], "warning");
+    assert_eq!(value["code"], serde_json::Value::Null);
+    assert_eq!(value["span"], serde_json::Value::Null);
+    assert_eq!(value["labels"], json!([]));
+    assert_eq!(value["notes"], json!([]));
+    assert_eq!(value["suggestions"], json!([]));
+}
+
+#[test]
+fn columns_count_chars_in_multi_line_spans() {
+    let source = "let s = \"é\n  ü\";";
+    let diagnostic = Diagnostic::error("Unterminated string").with_label(Span::new(8, source.len() - 1), "string");
+    let span = &to_json(&diagnostic, "main.xta", &LineIndex::new(source))["span"];
+
+    assert_eq!(
+        *span,
+        json!({"start": 8, "end": 17, "line_start": 1, "column_start": 9, "line_end": 2, "column_end": 5})
+    );
+}
@@ -0,0 +1,109 @@
+use xta_diagnostics::{diagnostic::Diagnostic, renderer::Renderer};
+use xta_lexer::{line_index::LineIndex, token::Span};
+
+fn render(diagnostic: &Diagnostic, source: &str) -> String {
+    Renderer::new(false).render(diagnostic, "main.xta", &LineIndex::new(source))
+}
+
+#[test]
+fn label_is_underlined_below_its_line() {
+    let source = "fn main() {\n    let a = 5\n}\n";
+    let diagnostic = Diagnostic::error("Expected `;`, found `}`")
+        .with_code("XP0001")
+        .with_label(Span::new(25, 26), "expected `;`");
+
+    assert_eq!(
+        render(&diagnostic, source),
+        "error[XP0001]: Expected `;`, found `}`\n \
+         --> main.xta:2:14\n  \
+           |\n\
+         2 |     let a = 5\n  \
+           |              ^ expected `;`\n"
+    );
+}
+
+#[test]
+fn secondary_labels_notes_and_suggestions() {
+    let source = "const limit = 10;\n\nfn main() {\n    limit = 20;\n}\n";
+    let diagnostic = Diagnostic::error("Cannot assign to constant `limit`")
+        .with_code("XA0009")
+        .with_label(Span::new(35, 40), "assigned here")
+        .with_secondary(Span::new(0, 17), "declared here")
+        .with_note("constants cannot change after they are declared")
+        .with_suggestion(Span::new(0, 5), "let", "declare it with");
+
+    assert_eq!(
+        render(&diagnostic, source),
+        "error[XA0009]: Cannot assign to constant `limit`\n \
+         --> main.xta:4:5\n  \
+           |\n\
+         1 | const limit = 10;\n  \
+           | ----------------- declared here\n\
+         ...\n\
+         4 |     limit = 20;\n  \
+           |     ^^^^^ assigned here\n  \
+           = note: constants cannot change after they are declared\n  \
+           = help: declare it with: `let`\n"
+    );
+}
+
+#[test]
+fn multi_line_span_is_underlined_to_the_end_of_its_first_line() {
+    let source = "fn main() {\n    print(1 +\n        2);\n}\n";
+    let diagnostic = Diagnostic::error("Type mismatch").with_label(Span::new(22, 38), "this expression");
+
+    assert_eq!(
+        render(&diagnostic, source),
+        "error: Type mismatch\n \
+         --> main.xta:2:11\n  \
+           |\n\
+         2 |     print(1 +\n  \
+           |           ^^^ this expression\n"
+    );
+}
+
+#[test]
+fn tabs_are_kept_and_columns_count_characters() {
+    let source = "fn main() {\n\tlet é = \"ü\" + 1;\n}\n";
+    let start = source.find("\"ü\"").unwrap();
+    let diagnostic = Diagnostic::error("Type mismatch").with_label(Span::new(start, start + "\"ü\"".len()), "a string");
+
+    assert_eq!(
+        render(&diagnostic, source),
+        "error: Type mismatch\n \
+         --> main.xta:2:10\n  \
+           |\n\
+         2 | \tlet é = \"ü\" + 1;\n  \
+           | \t        ^^^ a string\n"
+    );
+}
+
+#[test]
+fn warnings_and_diagnostics_without_labels() {
+    let warning = Diagnostic::warning("Unreachable code").with_label(Span::new(0, 5), "never runs");
+    assert_eq!(
+        render(&warning, "print(1);\n"),
+        "warning: Unreachable code\n \
+         --> main.xta:1:1\n  \
+           |\n\
+         1 | print(1);\n  \
+           | ^^^^^ never runs\n"
+    );
+
+    let error = Diagnostic::error("No `main` function was found").with_note("every program starts at `fn main()`");
+    assert_eq!(
+        render(&error, "fn start() {}\n"),
+        "error: No `main` function was found\n\
+         --> main.xta\n \
+          = note: every program starts at `fn main()`\n"
+    );
+}
+
+#[test]
+fn color_wraps_the_output_in_escape_codes() {
+    let diagnostic = Diagnostic::warning("Unused").with_label(Span::new(0, 1), "here");
+    let rendered = Renderer::new(true).render(&diagnostic, "main.xta", &LineIndex::new("a\n"));
+
+    assert!(rendered.starts_with("\u{1b}[1;33mwarning\u{1b}[0m"), "found {rendered:?}");
+    assert!(rendered.contains("\u{1b}[1;33m^ here\u{1b}[0m"), "found {rendered:?}");
+}
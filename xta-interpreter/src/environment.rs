@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use crate::value::Value;
+
+// a single function invocation, holding its nested block scopes
+pub struct Frame<'a> {
+    scopes: Vec<HashMap<&'a str, Value>>,
+}
+
+impl<'a> Frame<'a> {
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![HashMap::new()],
+        }
+    }
+
+    pub fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    // declares a variable in the innermost scope, shadowing any outer one
+    pub fn declare(&mut self, name: &'a str, value: Value) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, value);
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    // updates the nearest variable with the given name, returns false if none exists
+    pub fn assign(&mut self, name: &str, value: Value) -> bool {
+        match self.scopes.iter_mut().rev().find_map(|scope| scope.get_mut(name)) {
+            Some(slot) => {
+                *slot = value;
+                true
+            }
+            None => false,
+        }
+    }
+}
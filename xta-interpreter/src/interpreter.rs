@@ -0,0 +1,648 @@
+use std::{collections::HashMap, sync::Arc, thread};
+
+use xta_diagnostics::diagnostic::Diagnostic;
+use xta_lexer::{line_index::LineIndex, token::Loc};
+use xta_parser::ast::{BinaryExpr, BinaryOpType, Block, CallExpr, Expr, ForStmt, FunctionDeclStmt, IfStmt, Literal, Stmt, UnaryExpr, UnaryOpType, VarDeclStmt};
+
+use crate::{environment::Frame, value::Value};
+
+// how deep the interpreted program may recurse before it is stopped
+const MAX_CALL_DEPTH: usize = 1024;
+
+// every statement and expression is executed recursively on the host stack, so the nesting of
+// all active calls together is limited as well, the program runs on its own thread with a
+// stack large enough for it
+const MAX_NESTING: usize = 16 * 1024;
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+pub struct Interpreter<'a> {
+    functions: HashMap<&'a str, Arc<FunctionDeclStmt<'a>>>,
+    consts: Vec<VarDeclStmt<'a>>,
+    globals: HashMap<&'a str, Value>,
+    frames: Vec<Frame<'a>>,
+    // how many statements and expressions are currently being executed inside each other
+    nesting: usize,
+}
+
+// the outcome of executing a statement
+enum Flow {
+    Normal,
+    Break,
+    Continue,
+    Return(Value),
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(stmts: Vec<Stmt<'a>>) -> Self {
+        let mut functions = HashMap::new();
+        let mut consts = Vec::new();
+
+        for stmt in stmts {
+            match stmt {
+                Stmt::FunctionDecl(func) => {
+                    functions.insert(func.name, Arc::new(func));
+                }
+                Stmt::VarDecl(decl) if decl.is_const => consts.push(decl),
+                _ => {}
+            }
+        }
+
+        Self {
+            functions,
+            consts,
+            globals: HashMap::new(),
+            frames: Vec::new(),
+            nesting: 0,
+        }
+    }
+
+    // runs the program by calling its `main` function
+    pub fn run(&mut self) -> Result<Value, Error> {
+        thread::scope(|scope| {
+            let program = thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn_scoped(scope, || self.run_main())
+                .expect("failed to spawn the interpreter thread");
+
+            program.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })
+    }
+}
+
+// Private functions
+impl<'a> Interpreter<'a> {
+    fn run_main(&mut self) -> Result<Value, Error> {
+        self.init_globals()?;
+
+        let loc = Loc { row: 0, col: 0 };
+        let main = self.functions.get("main").cloned().ok_or(Error::NoMain)?;
+
+        self.call_function(main, Vec::new(), loc)
+    }
+
+    fn frame(&mut self) -> &mut Frame<'a> {
+        self.frames.last_mut().expect("no active call frame")
+    }
+
+    // locals shadow the top-level constants
+    fn lookup(&self, name: &str) -> Option<&Value> {
+        self.frames.last().and_then(|frame| frame.get(name)).or_else(|| self.globals.get(name))
+    }
+
+    // evaluates the top-level constants in declaration order
+    fn init_globals(&mut self) -> Result<(), Error> {
+        let consts = std::mem::take(&mut self.consts);
+
+        self.frames.push(Frame::new());
+        for decl in &consts {
+            let value = match &decl.value {
+                Some(expr) => self.eval(expr),
+                None => Ok(Value::None),
+            };
+
+            match value {
+                Ok(value) => {
+                    self.globals.insert(decl.name, value);
+                }
+                Err(error) => {
+                    self.frames.pop();
+                    return Err(error);
+                }
+            }
+        }
+        self.frames.pop();
+
+        Ok(())
+    }
+
+    fn call_function(&mut self, func: Arc<FunctionDeclStmt<'a>>, args: Vec<Value>, loc: Loc) -> Result<Value, Error> {
+        if func.params.len() != args.len() {
+            return Err(Error::ArgumentCount {
+                loc,
+                name: func.name.to_string(),
+                expected: func.params.len(),
+                found: args.len(),
+            });
+        }
+
+        if self.frames.len() >= MAX_CALL_DEPTH {
+            return Err(Error::StackOverflow { loc });
+        }
+
+        let mut frame = Frame::new();
+        for (param, arg) in func.params.iter().zip(args) {
+            frame.declare(param.name, arg);
+        }
+
+        self.frames.push(frame);
+        let result = self.exec_stmts(&func.body);
+        self.frames.pop();
+
+        match result? {
+            Flow::Return(value) => Ok(value),
+            // the analyzer rejects `break` and `continue` outside of a loop
+            Flow::Normal | Flow::Break | Flow::Continue => Ok(Value::None),
+        }
+    }
+
+    // executes a block inside a fresh scope
+    fn exec_block(&mut self, block: &Block<'a>) -> Result<Flow, Error> {
+        self.frame().push_scope();
+        let result = self.exec_stmts(block);
+        self.frame().pop_scope();
+
+        result
+    }
+
+    fn exec_stmts(&mut self, stmts: &[Stmt<'a>]) -> Result<Flow, Error> {
+        for stmt in stmts {
+            match self.exec_stmt(stmt)? {
+                Flow::Normal => {}
+                flow => return Ok(flow),
+            }
+        }
+
+        Ok(Flow::Normal)
+    }
+
+    fn exec_stmt(&mut self, stmt: &Stmt<'a>) -> Result<Flow, Error> {
+        self.enter(stmt.loc())?;
+        let result = self.exec_stmt_inner(stmt);
+        self.nesting -= 1;
+
+        result
+    }
+
+    fn exec_stmt_inner(&mut self, stmt: &Stmt<'a>) -> Result<Flow, Error> {
+        match stmt {
+            Stmt::VarDecl(decl) => {
+                let value = match &decl.value {
+                    Some(expr) => self.eval(expr)?,
+                    None => Value::None,
+                };
+                self.frame().declare(decl.name, value);
+                Ok(Flow::Normal)
+            }
+            Stmt::If(stmt) => self.exec_if(stmt),
+            Stmt::Return(stmt) => {
+                let value = match &stmt.value {
+                    Some(expr) => self.eval(expr)?,
+                    None => Value::None,
+                };
+                Ok(Flow::Return(value))
+            }
+            Stmt::While(stmt) => {
+                while self.eval_condition(&stmt.condition)? {
+                    match self.exec_block(&stmt.body)? {
+                        Flow::Break => break,
+                        Flow::Normal | Flow::Continue => {}
+                        flow => return Ok(flow),
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+            Stmt::For(stmt) => self.exec_for(stmt),
+            Stmt::Loop(stmt) => {
+                loop {
+                    match self.exec_block(&stmt.body)? {
+                        Flow::Break => break,
+                        Flow::Normal | Flow::Continue => {}
+                        flow => return Ok(flow),
+                    }
+
+                    if let Some(condition) = &stmt.unless {
+                        if self.eval_condition(condition)? {
+                            break;
+                        }
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+            Stmt::Break(_) => Ok(Flow::Break),
+            Stmt::Continue(_) => Ok(Flow::Continue),
+            Stmt::Expr(expr) => {
+                self.eval(expr)?;
+                Ok(Flow::Normal)
+            }
+            // nested functions are not part of the language
+            Stmt::FunctionDecl(_) => Ok(Flow::Normal),
+        }
+    }
+
+    fn exec_if(&mut self, stmt: &IfStmt<'a>) -> Result<Flow, Error> {
+        if self.eval_condition(&stmt.condition)? {
+            return self.exec_block(&stmt.then);
+        }
+
+        for elif in &stmt.elif_branch {
+            if self.eval_condition(&elif.condition)? {
+                return self.exec_block(&elif.then);
+            }
+        }
+
+        match &stmt.else_branch {
+            Some(block) => self.exec_block(block),
+            None => Ok(Flow::Normal),
+        }
+    }
+
+    fn exec_for(&mut self, stmt: &ForStmt<'a>) -> Result<Flow, Error> {
+        let start = self.eval_bound(&stmt.start)?;
+        let end = self.eval_bound(&stmt.end)?;
+
+        let mut i = start;
+        while i < end || (stmt.inclusive && i == end) {
+            self.frame().push_scope();
+            self.frame().declare(stmt.var.name, Value::Integer(i));
+            let result = self.exec_block(&stmt.body);
+            self.frame().pop_scope();
+
+            match result? {
+                Flow::Break => break,
+                Flow::Normal | Flow::Continue => {}
+                flow => return Ok(flow),
+            }
+
+            // an inclusive range may end at the largest integer
+            match i.checked_add(1) {
+                Some(next) => i = next,
+                None => break,
+            }
+        }
+
+        Ok(Flow::Normal)
+    }
+
+    fn eval_bound(&mut self, expr: &Expr<'a>) -> Result<i64, Error> {
+        match self.eval(expr)? {
+            Value::Integer(value) => Ok(value),
+            value => Err(Error::ExpectedInt { loc: expr.loc(), found: value.type_name() }),
+        }
+    }
+
+    fn eval_condition(&mut self, expr: &Expr<'a>) -> Result<bool, Error> {
+        match self.eval(expr)? {
+            Value::Boolean(value) => Ok(value),
+            value => Err(Error::ExpectedBool { loc: expr.loc(), found: value.type_name() }),
+        }
+    }
+
+    fn eval(&mut self, expr: &Expr<'a>) -> Result<Value, Error> {
+        self.enter(expr.loc())?;
+        let result = self.eval_inner(expr);
+        self.nesting -= 1;
+
+        result
+    }
+
+    // counts one more level of nesting, failing once the host stack could run out
+    fn enter(&mut self, loc: Loc) -> Result<(), Error> {
+        if self.nesting >= MAX_NESTING {
+            return Err(Error::NestingTooDeep { loc });
+        }
+
+        self.nesting += 1;
+        Ok(())
+    }
+
+    fn eval_inner(&mut self, expr: &Expr<'a>) -> Result<Value, Error> {
+        match expr {
+            Expr::Literal(literal) => Ok(match &literal.value {
+                Literal::Integer(value) => Value::Integer(*value),
+                Literal::Double(value) => Value::Double(*value),
+                Literal::String(value) => Value::String(value.to_string()),
+                Literal::Boolean(value) => Value::Boolean(*value),
+                Literal::None => Value::None,
+            }),
+            Expr::Identifier(id) => {
+                let loc = id.loc.clone();
+                self.lookup(id.name).cloned().ok_or(Error::UndefinedVariable { loc, name: id.name.to_string() })
+            }
+            Expr::Unary(expr) => self.eval_unary(expr),
+            Expr::Binary(expr) => self.eval_binary(expr),
+            Expr::Call(call) => self.eval_call(call),
+            Expr::Error(expr) => Err(Error::Malformed { loc: expr.loc.clone() }),
+        }
+    }
+
+    fn eval_call(&mut self, call: &CallExpr<'a>) -> Result<Value, Error> {
+        let mut args = Vec::with_capacity(call.args.len());
+        for arg in &call.args {
+            args.push(self.eval(arg)?);
+        }
+
+        if let Some(func) = self.functions.get(call.name).cloned() {
+            return self.call_function(func, args, call.loc.clone());
+        }
+
+        match call.name {
+            "print" => {
+                let line: Vec<String> = args.iter().map(Value::to_string).collect();
+                println!("{}", line.join(" "));
+                Ok(Value::None)
+            }
+            _ => Err(Error::UndefinedFunction { loc: call.loc.clone(), name: call.name.to_string() }),
+        }
+    }
+
+    fn eval_unary(&mut self, expr: &UnaryExpr<'a>) -> Result<Value, Error> {
+        if expr.operator.is_update() {
+            return self.eval_update(expr);
+        }
+
+        let operand = self.eval(&expr.operand)?;
+
+        match (&expr.operator, operand) {
+            (UnaryOpType::Neg, Value::Integer(value)) => value.checked_neg().map(Value::Integer).ok_or(Error::Overflow { loc: expr.loc.clone() }),
+            (UnaryOpType::Neg, Value::Double(value)) => Ok(Value::Double(-value)),
+            (UnaryOpType::Not, Value::Boolean(value)) => Ok(Value::Boolean(!value)),
+            (UnaryOpType::BitNot, Value::Integer(value)) => Ok(Value::Integer(!value)),
+            (operator, value) => Err(Error::InvalidUnaryOperand {
+                loc: expr.loc.clone(),
+                operator: operator.to_string(),
+                found: value.type_name(),
+            }),
+        }
+    }
+
+    fn eval_binary(&mut self, expr: &BinaryExpr<'a>) -> Result<Value, Error> {
+        match expr.operator {
+            BinaryOpType::Assign => return self.eval_assign(expr),
+            BinaryOpType::And | BinaryOpType::Or => return self.eval_logical(expr),
+            _ => {}
+        }
+
+        if let Some(base) = expr.operator.compound_base() {
+            return self.eval_compound(expr, &base);
+        }
+
+        let left = self.eval(&expr.left)?;
+        let right = self.eval(&expr.right)?;
+        eval_operator(&expr.operator, left, right, &expr.loc)
+    }
+
+    // `&&` and `||` only evaluate their right side when needed
+    fn eval_logical(&mut self, expr: &BinaryExpr<'a>) -> Result<Value, Error> {
+        let left = self.eval_condition(&expr.left)?;
+
+        let short_circuit = match expr.operator {
+            BinaryOpType::And => !left,
+            _ => left,
+        };
+
+        if short_circuit {
+            return Ok(Value::Boolean(left));
+        }
+
+        Ok(Value::Boolean(self.eval_condition(&expr.right)?))
+    }
+
+    // `++` and `--`, the prefix forms evaluate to the new value and the postfix forms to the old one
+    fn eval_update(&mut self, expr: &UnaryExpr<'a>) -> Result<Value, Error> {
+        let Expr::Identifier(id) = expr.operand.as_ref() else {
+            return Err(Error::InvalidAssignTarget { loc: expr.operand.loc() });
+        };
+
+        let old = self.eval(&expr.operand)?;
+        let step = match expr.operator {
+            UnaryOpType::Inc | UnaryOpType::PostInc => 1,
+            _ => -1,
+        };
+
+        let new = match old {
+            Value::Integer(value) => value.checked_add(step).map(Value::Integer).ok_or(Error::Overflow { loc: expr.loc.clone() })?,
+            Value::Double(value) => Value::Double(value + step as f64),
+            value => {
+                return Err(Error::InvalidUnaryOperand {
+                    loc: expr.loc.clone(),
+                    operator: expr.operator.to_string(),
+                    found: value.type_name(),
+                })
+            }
+        };
+
+        self.frame().assign(id.name, new.clone());
+
+        match expr.operator {
+            UnaryOpType::Inc | UnaryOpType::Dec => Ok(new),
+            _ => Ok(old),
+        }
+    }
+
+    fn eval_assign(&mut self, expr: &BinaryExpr<'a>) -> Result<Value, Error> {
+        let Expr::Identifier(id) = expr.left.as_ref() else {
+            return Err(Error::InvalidAssignTarget { loc: expr.left.loc() });
+        };
+
+        let value = self.eval(&expr.right)?;
+        if !self.frame().assign(id.name, value.clone()) {
+            return Err(Error::UndefinedVariable { loc: id.loc.clone(), name: id.name.to_string() });
+        }
+
+        Ok(value)
+    }
+
+    // `a <op>= b` evaluates `a <op> b` and stores the result into `a`
+    fn eval_compound(&mut self, expr: &BinaryExpr<'a>, base: &BinaryOpType) -> Result<Value, Error> {
+        let Expr::Identifier(id) = expr.left.as_ref() else {
+            return Err(Error::InvalidAssignTarget { loc: expr.left.loc() });
+        };
+
+        let left = self.eval(&expr.left)?;
+        let right = self.eval(&expr.right)?;
+        let value = eval_operator(base, left, right, &expr.loc)?;
+
+        self.frame().assign(id.name, value.clone());
+        Ok(value)
+    }
+}
+
+// applies a non-assigning, non-logical operator to two evaluated operands
+fn eval_operator(operator: &BinaryOpType, left: Value, right: Value, loc: &Loc) -> Result<Value, Error> {
+    let invalid = |left: &Value, right: &Value| Error::InvalidOperands {
+        loc: loc.clone(),
+        operator: operator.to_string(),
+        left: left.type_name(),
+        right: right.type_name(),
+    };
+
+    match (&left, &right) {
+        (Value::Integer(l), Value::Integer(r)) => eval_integer(operator, *l, *r, loc),
+        (Value::Double(_), Value::Double(_)) | (Value::Integer(_), Value::Double(_)) | (Value::Double(_), Value::Integer(_)) => {
+            let (l, r) = (as_double(&left), as_double(&right));
+            eval_double(operator, l, r).ok_or_else(|| invalid(&left, &right))
+        }
+        (Value::String(l), Value::String(r)) => match operator {
+            BinaryOpType::Add => Ok(Value::String(format!("{}{}", l, r))),
+            BinaryOpType::Eq => Ok(Value::Boolean(l == r)),
+            BinaryOpType::Neq => Ok(Value::Boolean(l != r)),
+            BinaryOpType::Smaller => Ok(Value::Boolean(l < r)),
+            BinaryOpType::Greater => Ok(Value::Boolean(l > r)),
+            BinaryOpType::SmallerEq => Ok(Value::Boolean(l <= r)),
+            BinaryOpType::GreaterEq => Ok(Value::Boolean(l >= r)),
+            _ => Err(invalid(&left, &right)),
+        },
+        _ => match operator {
+            BinaryOpType::Eq => Ok(Value::Boolean(left == right)),
+            BinaryOpType::Neq => Ok(Value::Boolean(left != right)),
+            _ => Err(invalid(&left, &right)),
+        },
+    }
+}
+
+fn eval_integer(operator: &BinaryOpType, l: i64, r: i64, loc: &Loc) -> Result<Value, Error> {
+    let overflow = || Error::Overflow { loc: loc.clone() };
+
+    let value = match operator {
+        BinaryOpType::Add => Value::Integer(l.checked_add(r).ok_or_else(overflow)?),
+        BinaryOpType::Sub => Value::Integer(l.checked_sub(r).ok_or_else(overflow)?),
+        BinaryOpType::Mul => Value::Integer(l.checked_mul(r).ok_or_else(overflow)?),
+        BinaryOpType::Div => {
+            if r == 0 {
+                return Err(Error::DivisionByZero { loc: loc.clone() });
+            }
+            Value::Integer(l.checked_div(r).ok_or_else(overflow)?)
+        }
+        BinaryOpType::Mod => {
+            if r == 0 {
+                return Err(Error::DivisionByZero { loc: loc.clone() });
+            }
+            Value::Integer(l.checked_rem(r).ok_or_else(overflow)?)
+        }
+        BinaryOpType::Pow => {
+            if r < 0 {
+                return Err(Error::NegativeExponent { loc: loc.clone() });
+            }
+            Value::Integer(u32::try_from(r).ok().and_then(|r| l.checked_pow(r)).ok_or_else(overflow)?)
+        }
+        BinaryOpType::BitAnd => Value::Integer(l & r),
+        BinaryOpType::BitOr => Value::Integer(l | r),
+        BinaryOpType::BitXor => Value::Integer(l ^ r),
+        BinaryOpType::LShift => Value::Integer(shift_amount(r).and_then(|r| l.checked_shl(r)).ok_or_else(overflow)?),
+        BinaryOpType::RShift => Value::Integer(shift_amount(r).and_then(|r| l.checked_shr(r)).ok_or_else(overflow)?),
+        BinaryOpType::Eq => Value::Boolean(l == r),
+        BinaryOpType::Neq => Value::Boolean(l != r),
+        BinaryOpType::Smaller => Value::Boolean(l < r),
+        BinaryOpType::Greater => Value::Boolean(l > r),
+        BinaryOpType::SmallerEq => Value::Boolean(l <= r),
+        BinaryOpType::GreaterEq => Value::Boolean(l >= r),
+        BinaryOpType::And
+        | BinaryOpType::Or
+        | BinaryOpType::Assign
+        | BinaryOpType::AddAssign
+        | BinaryOpType::SubAssign
+        | BinaryOpType::MulAssign
+        | BinaryOpType::DivAssign
+        | BinaryOpType::ModAssign
+        | BinaryOpType::BitAndAssign
+        | BinaryOpType::BitOrAssign
+        | BinaryOpType::BitXorAssign
+        | BinaryOpType::LShiftAssign
+        | BinaryOpType::RShiftAssign => unreachable!("handled before operand evaluation"),
+    };
+
+    Ok(value)
+}
+
+fn eval_double(operator: &BinaryOpType, l: f64, r: f64) -> Option<Value> {
+    Some(match operator {
+        BinaryOpType::Add => Value::Double(l + r),
+        BinaryOpType::Sub => Value::Double(l - r),
+        BinaryOpType::Mul => Value::Double(l * r),
+        BinaryOpType::Div => Value::Double(l / r),
+        BinaryOpType::Mod => Value::Double(l % r),
+        BinaryOpType::Pow => Value::Double(l.powf(r)),
+        BinaryOpType::Eq => Value::Boolean(l == r),
+        BinaryOpType::Neq => Value::Boolean(l != r),
+        BinaryOpType::Smaller => Value::Boolean(l < r),
+        BinaryOpType::Greater => Value::Boolean(l > r),
+        BinaryOpType::SmallerEq => Value::Boolean(l <= r),
+        BinaryOpType::GreaterEq => Value::Boolean(l >= r),
+        _ => return None,
+    })
+}
+
+fn as_double(value: &Value) -> f64 {
+    match value {
+        Value::Integer(value) => *value as f64,
+        Value::Double(value) => *value,
+        _ => unreachable!("only called on numeric values"),
+    }
+}
+
+fn shift_amount(r: i64) -> Option<u32> {
+    u32::try_from(r).ok()
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum Error {
+    #[error("~ Error: No `main` function was found")]
+    NoMain,
+
+    #[error("~ ({loc}) : Use of undefined variable `{name}`")]
+    UndefinedVariable { loc: Loc, name: String },
+
+    #[error("~ ({loc}) : Call to undefined function `{name}`")]
+    UndefinedFunction { loc: Loc, name: String },
+
+    #[error("~ ({loc}) : Function `{name}` takes {expected} argument(s), but {found} were given")]
+    ArgumentCount { loc: Loc, name: String, expected: usize, found: usize },
+
+    #[error("~ ({loc}) : Expected a `Bool` condition, found `{found}`")]
+    ExpectedBool { loc: Loc, found: &'static str },
+
+    #[error("~ ({loc}) : Expected an `Int` range bound, found `{found}`")]
+    ExpectedInt { loc: Loc, found: &'static str },
+
+    #[error("~ ({loc}) : Cannot apply `{operator}` to `{left}` and `{right}`")]
+    InvalidOperands { loc: Loc, operator: String, left: &'static str, right: &'static str },
+
+    #[error("~ ({loc}) : Cannot apply `{operator}` to `{found}`")]
+    InvalidUnaryOperand { loc: Loc, operator: String, found: &'static str },
+
+    #[error("~ ({loc}) : Invalid assignment target")]
+    InvalidAssignTarget { loc: Loc },
+
+    #[error("~ ({loc}) : Division by zero")]
+    DivisionByZero { loc: Loc },
+
+    #[error("~ ({loc}) : Cannot raise an `Int` to a negative power")]
+    NegativeExponent { loc: Loc },
+
+    #[error("~ ({loc}) : Integer overflow")]
+    Overflow { loc: Loc },
+
+    #[error("~ ({loc}) : Maximum call depth exceeded")]
+    StackOverflow { loc: Loc },
+
+    #[error("~ ({loc}) : Statements and expressions are nested too deeply across the active calls")]
+    NestingTooDeep { loc: Loc },
+
+    #[error("~ ({loc}) : Cannot run code that failed to parse")]
+    Malformed { loc: Loc },
+}
+
+impl Error {
+    // runtime errors only know the position they were raised at, so the label marks a single char
+    pub fn to_diagnostic(&self, index: &LineIndex) -> Diagnostic {
+        let (loc, message) = match self {
+            Error::NoMain => return Diagnostic::error("No `main` function was found"),
+            Error::UndefinedVariable { loc, name } => (loc, format!("Use of undefined variable `{name}`")),
+            Error::UndefinedFunction { loc, name } => (loc, format!("Call to undefined function `{name}`")),
+            Error::ArgumentCount { loc, name, expected, found } => (loc, format!("Function `{name}` takes {expected} argument(s), but {found} were given")),
+            Error::ExpectedBool { loc, found } => (loc, format!("Expected a `Bool` condition, found `{found}`")),
+            Error::ExpectedInt { loc, found } => (loc, format!("Expected an `Int` range bound, found `{found}`")),
+            Error::InvalidOperands { loc, operator, left, right } => (loc, format!("Cannot apply `{operator}` to `{left}` and `{right}`")),
+            Error::InvalidUnaryOperand { loc, operator, found } => (loc, format!("Cannot apply `{operator}` to `{found}`")),
+            Error::InvalidAssignTarget { loc } => (loc, "Invalid assignment target".to_string()),
+            Error::DivisionByZero { loc } => (loc, "Division by zero".to_string()),
+            Error::NegativeExponent { loc } => (loc, "Cannot raise an `Int` to a negative power".to_string()),
+            Error::Overflow { loc } => (loc, "Integer overflow".to_string()),
+            Error::StackOverflow { loc } => (loc, "Maximum call depth exceeded".to_string()),
+            Error::NestingTooDeep { loc } => (loc, "Statements and expressions are nested too deeply across the active calls".to_string()),
+            Error::Malformed { loc } => (loc, "Cannot run code that failed to parse".to_string()),
+        };
+
+        Diagnostic::error(message).with_label(index.char_span(loc), "raised here")
+    }
+}
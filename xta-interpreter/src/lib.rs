@@ -0,0 +1,3 @@
+pub mod value;
+pub mod interpreter;
+mod environment;
\ No newline at end of file
@@ -0,0 +1,34 @@
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Integer(i64),
+    Double(f64),
+    String(String),
+    Boolean(bool),
+    None,
+}
+
+impl Value {
+    // the name of the value's type, used for runtime errors
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Integer(_) => "Int",
+            Value::Double(_) => "Double",
+            Value::String(_) => "String",
+            Value::Boolean(_) => "Bool",
+            Value::None => "None",
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Value::Integer(value) => write!(f, "{}", value),
+            // `{:?}` keeps the fraction, so `1.0` does not print like the `Int` `1`
+            Value::Double(value) => write!(f, "{:?}", value),
+            Value::String(value) => write!(f, "{}", value),
+            Value::Boolean(value) => write!(f, "{}", value),
+            Value::None => write!(f, "None"),
+        }
+    }
+}
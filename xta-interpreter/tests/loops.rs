@@ -0,0 +1,124 @@
+use xta_interpreter::{interpreter::{Error, Interpreter}, value::Value};
+use xta_lexer::scanner::Scanner;
+use xta_parser::parser::Parser;
+
+fn run(source: &str) -> Result<Value, Error> {
+    let mut parser = Parser::new(Scanner::new(source));
+    let stmts = parser.parse_file();
+    assert!(parser.errors.is_empty(), "unexpected parse errors: {:?}", parser.errors);
+
+    Interpreter::new(stmts).run()
+}
+
+// the `Int` returned by a `main` made of the given statements
+fn main_returns(body: &str) -> i64 {
+    match run(&format!("fn main() -> Int {{ {body} }}")) {
+        Ok(Value::Integer(value)) => value,
+        result => panic!("`{body}`: unexpected result {:?}", result),
+    }
+}
+
+#[test]
+fn while_checks_its_condition_before_every_iteration() {
+    assert_eq!(main_returns("let n = 0; let checks = 0; while (checks += 1) < 4 { n += 1; } return n * 10 + checks;"), 34);
+    assert_eq!(main_returns("let n = 0; while false { n += 1; } return n;"), 0);
+}
+
+#[test]
+fn while_with_break_and_continue() {
+    // adds the odd numbers below 7
+    let body = "
+        let i = 0;
+        let sum = 0;
+        while true {
+            i++;
+            if i >= 7 { break; }
+            if i % 2 == 0 { continue; }
+            sum += i;
+        }
+        return sum;
+    ";
+    assert_eq!(main_returns(body), 1 + 3 + 5);
+
+    // `break` only leaves the innermost loop
+    let body = "
+        let outer = 0;
+        let inner = 0;
+        while outer < 3 {
+            outer++;
+            while true { inner++; break; }
+        }
+        return outer * 10 + inner;
+    ";
+    assert_eq!(main_returns(body), 33);
+
+    let result = run("fn main() { while 1 { } }");
+    assert!(matches!(result, Err(Error::ExpectedBool { .. })), "unexpected result: {:?}", result);
+}
+
+#[test]
+fn for_counts_through_its_range() {
+    assert_eq!(main_returns("let n = 0; for i in 0..5 { n += 1; } return n;"), 5);
+    assert_eq!(main_returns("let n = 0; for i in 0..=5 { n += 1; } return n;"), 6);
+    assert_eq!(main_returns("let sum = 0; for i in 2..=4 { sum = sum * 10 + i; } return sum;"), 234);
+    assert_eq!(main_returns("let n = 0; for i in 3..=3 { n += 1; } return n;"), 1);
+
+    // empty ranges never run the body
+    assert_eq!(main_returns("let n = 0; for i in 3..0 { n += 1; } return n;"), 0);
+    assert_eq!(main_returns("let n = 0; for i in 3..3 { n += 1; } return n;"), 0);
+    assert_eq!(main_returns("let n = 0; for i in 3..=2 { n += 1; } return n;"), 0);
+}
+
+#[test]
+fn for_bounds_are_evaluated_once() {
+    assert_eq!(main_returns("let n = 4; let runs = 0; for i in 0..n { n -= 1; runs += 1; } return runs * 10 + n;"), 40);
+}
+
+#[test]
+fn for_with_break_continue_and_return() {
+    assert_eq!(main_returns("let sum = 0; for i in 0..10 { if i == 5 { break; } sum += i; } return sum;"), 1 + 2 + 3 + 4);
+    assert_eq!(main_returns("let sum = 0; for i in 0..10 { if i % 3 != 0 { continue; } sum += i; } return sum;"), 3 + 6 + 9);
+    assert_eq!(main_returns("for i in 1..10 { if i * i > 20 { return i; } } return 0;"), 5);
+
+    // the loop variable of an inclusive range may end at the largest integer
+    assert_eq!(main_returns("let n = 0; for i in 9223372036854775806..=9223372036854775807 { n += 1; } return n;"), 2);
+
+    let result = run("fn main() { for i in 0..\"3\" { } }");
+    assert!(matches!(result, Err(Error::ExpectedInt { .. })), "unexpected result: {:?}", result);
+}
+
+#[test]
+fn loop_runs_until_break() {
+    assert_eq!(main_returns("let n = 0; loop { n += 1; if n > 3 { break; } } return n;"), 4);
+    assert_eq!(main_returns("let n = 0; loop { n += 1; if n == 7 { return n * 2; } }"), 14);
+}
+
+#[test]
+fn unless_is_checked_after_every_iteration() {
+    // the digits record the order: 1 for the body, 2 for the condition
+    assert_eq!(main_returns("let trace = 0; loop { trace = trace * 10 + 1; } unless (trace = trace * 10 + 2) > 1000; return trace;"), 1212);
+
+    // the body always runs once, even when the condition already holds
+    assert_eq!(main_returns("let n = 0; loop { n += 1; } unless true; return n;"), 1);
+}
+
+#[test]
+fn unless_with_break_and_continue() {
+    // `continue` still reaches the condition
+    let body = "
+        let n = 0;
+        let checks = 0;
+        loop {
+            n += 1;
+            if n < 3 { continue; }
+        } unless (checks += 1) >= 5;
+        return n * 10 + checks;
+    ";
+    assert_eq!(main_returns(body), 55);
+
+    // `break` leaves the loop without checking the condition
+    assert_eq!(main_returns("let checks = 0; loop { break; } unless (checks += 1) > 0; return checks;"), 0);
+
+    let result = run("fn main() { loop { } unless 0; }");
+    assert!(matches!(result, Err(Error::ExpectedBool { .. })), "unexpected result: {:?}", result);
+}
@@ -0,0 +1,106 @@
+use xta_interpreter::{interpreter::{Error, Interpreter}, value::Value};
+use xta_lexer::scanner::Scanner;
+use xta_parser::parser::Parser;
+
+fn run(source: &str) -> Result<Value, Error> {
+    let mut parser = Parser::new(Scanner::new(source));
+    let stmts = parser.parse_file();
+    assert!(parser.errors.is_empty(), "unexpected parse errors: {:?}", parser.errors);
+
+    Interpreter::new(stmts).run()
+}
+
+// the value `main` returns for the given expression
+fn eval(ty: &str, expr: &str) -> Value {
+    let result = run(&format!("fn main() -> {ty} {{ return {expr}; }}"));
+    result.unwrap_or_else(|error| panic!("`{expr}` failed: {error}"))
+}
+
+#[test]
+fn remainder_takes_the_sign_of_the_dividend() {
+    assert_eq!(eval("Int", "7 % 3"), Value::Integer(1));
+    assert_eq!(eval("Int", "-7 % 3"), Value::Integer(-1));
+    assert_eq!(eval("Int", "7 % -3"), Value::Integer(1));
+    assert_eq!(eval("Int", "-7 % -3"), Value::Integer(-1));
+    assert_eq!(eval("Double", "7.5 % 2"), Value::Double(1.5));
+    assert_eq!(eval("Double", "-7.5 % 2.0"), Value::Double(-1.5));
+
+    let result = run("fn main() -> Int { let zero = 0; return 1 % zero; }");
+    assert!(matches!(result, Err(Error::DivisionByZero { .. })), "unexpected result: {:?}", result);
+}
+
+#[test]
+fn exponent_is_right_associative() {
+    assert_eq!(eval("Int", "2 ** 10"), Value::Integer(1024));
+    assert_eq!(eval("Int", "2 ** 3 ** 2"), Value::Integer(512));
+    assert_eq!(eval("Int", "(2 ** 3) ** 2"), Value::Integer(64));
+    assert_eq!(eval("Int", "-2 ** 2"), Value::Integer(-4));
+    assert_eq!(eval("Int", "2 ** 0"), Value::Integer(1));
+    assert_eq!(eval("Double", "4.0 ** 0.5"), Value::Double(2.0));
+    assert_eq!(eval("Double", "2 ** -1.0"), Value::Double(0.5));
+}
+
+#[test]
+fn int_exponent_must_be_non_negative_and_fit() {
+    let result = run("fn main() -> Int { return 2 ** -1; }");
+    assert!(matches!(result, Err(Error::NegativeExponent { .. })), "unexpected result: {:?}", result);
+
+    let result = run("fn main() -> Int { return 2 ** 64; }");
+    assert!(matches!(result, Err(Error::Overflow { .. })), "unexpected result: {:?}", result);
+}
+
+#[test]
+fn prefix_and_postfix_updates() {
+    // postfix operators evaluate to the old value, prefix ones to the new value
+    let source = "
+        fn main() -> Int {
+            let i = 5;
+            let a = i++;
+            let b = ++i;
+            let c = i--;
+            let d = --i;
+            return a * 1000 + b * 100 + c * 10 + d;
+        }
+    ";
+    assert_eq!(run(source).unwrap(), Value::Integer(5 * 1000 + 7 * 100 + 7 * 10 + 5));
+
+    assert_eq!(run("fn main() -> Double { let d = 1.5; d++; return d; }").unwrap(), Value::Double(2.5));
+    assert_eq!(eval("Int", "~5"), Value::Integer(-6));
+    assert_eq!(eval("Bool", "!true"), Value::Boolean(false));
+
+    let result = run("fn main() { let s = \"a\"; s++; }");
+    assert!(matches!(result, Err(Error::InvalidUnaryOperand { .. })), "unexpected result: {:?}", result);
+}
+
+#[test]
+fn compound_assignments_store_their_result() {
+    let source = "
+        fn main() -> Int {
+            let x = 10;
+            x += 5;
+            x -= 1;
+            x *= 2;
+            x /= 3;
+            x %= 4;
+            x <<= 3;
+            x >>= 1;
+            x |= 1;
+            x &= 6;
+            x ^= 15;
+            return x;
+        }
+    ";
+    // 15, 14, 28, 9, 1, 8, 4, 5, 4, 11
+    assert_eq!(run(source).unwrap(), Value::Integer(11));
+
+    // an assignment is an expression, evaluating to the stored value
+    assert_eq!(run("fn main() -> Int { let x = 7; let y = x %= 4; return x * 10 + y; }").unwrap(), Value::Integer(33));
+    assert_eq!(run("fn main() -> Int { let x = 1; x <<= 4; return x; }").unwrap(), Value::Integer(16));
+}
+
+#[test]
+fn compound_assignment_widens_to_double() {
+    assert_eq!(run("fn main() -> Double { let d = 1.5; d += 1; return d; }").unwrap(), Value::Double(2.5));
+    assert_eq!(run("fn main() -> Double { let d = 2.0; d *= 3; return d; }").unwrap(), Value::Double(6.0));
+    assert_eq!(run("fn main() -> String { let s = \"a\"; s += \"b\"; return s; }").unwrap(), Value::String("ab".to_string()));
+}
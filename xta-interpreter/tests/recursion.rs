@@ -0,0 +1,69 @@
+use xta_interpreter::{interpreter::{Error, Interpreter}, value::Value};
+use xta_lexer::scanner::Scanner;
+use xta_parser::parser::Parser;
+
+fn run(source: &str) -> Result<Value, Error> {
+    let mut parser = Parser::new(Scanner::new(source));
+    let stmts = parser.parse_file();
+    assert!(parser.errors.is_empty(), "unexpected parse errors: {:?}", parser.errors);
+
+    Interpreter::new(stmts).run()
+}
+
+#[test]
+fn recursion_through_nested_blocks_completes() {
+    let source = "
+        fn down(n Int) -> Int {
+            if n > 0 {
+                while true {
+                    loop {
+                        if true {
+                            return down(n - 1) + 1;
+                        }
+                    }
+                }
+            }
+            return 0;
+        }
+
+        fn main() -> Int {
+            return down(1000);
+        }
+    ";
+
+    let result = run(source);
+    assert!(matches!(result, Ok(Value::Integer(1000))), "unexpected result: {:?}", result);
+}
+
+#[test]
+fn unbounded_recursion_is_stopped() {
+    let source = "
+        fn forever(n Int) -> Int {
+            return forever(n + 1);
+        }
+
+        fn main() {
+            forever(0);
+        }
+    ";
+
+    let result = run(source);
+    assert!(matches!(result, Err(Error::StackOverflow { .. })), "unexpected result: {:?}", result);
+}
+
+#[test]
+fn deeply_nested_recursion_is_stopped() {
+    let body = format!("{}return deep(n + 1);{}", "if true { ".repeat(60), " }".repeat(60));
+    let source = format!("fn deep(n Int) -> Int {{ {body} return 0; }} fn main() {{ deep(0); }}");
+
+    let result = run(&source);
+    assert!(matches!(result, Err(Error::NestingTooDeep { .. })), "unexpected result: {:?}", result);
+}
+
+#[test]
+fn long_expressions_are_not_recursion() {
+    let source = format!("fn main() -> Int {{ let x = 1; return x{}; }}", " + x".repeat(200));
+
+    let result = run(&source);
+    assert!(matches!(result, Ok(Value::Integer(201))), "unexpected result: {:?}", result);
+}
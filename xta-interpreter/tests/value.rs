@@ -0,0 +1,18 @@
+use xta_interpreter::value::Value;
+
+#[test]
+fn doubles_print_differently_from_ints() {
+    assert_eq!(Value::Integer(1).to_string(), "1");
+    assert_eq!(Value::Double(1.0).to_string(), "1.0");
+    assert_eq!(Value::Double(-2.0).to_string(), "-2.0");
+    assert_eq!(Value::Double(2.5).to_string(), "2.5");
+    assert_eq!(Value::Double(1e20).to_string(), "1e20");
+    assert_eq!(Value::Double(f64::INFINITY).to_string(), "inf");
+}
+
+#[test]
+fn other_values_print_as_written() {
+    assert_eq!(Value::String("a \"b\"".to_string()).to_string(), "a \"b\"");
+    assert_eq!(Value::Boolean(true).to_string(), "true");
+    assert_eq!(Value::None.to_string(), "None");
+}
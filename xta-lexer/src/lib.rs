@@ -1,2 +1,3 @@
 pub mod token;
-pub mod scanner;
\ No newline at end of file
+pub mod scanner;
+pub mod line_index;
\ No newline at end of file
@@ -0,0 +1,74 @@
+use crate::token::{Loc, Span};
+
+// maps byte offsets in the source back to lines and columns
+pub struct LineIndex<'a> {
+    source: &'a str,
+    // the byte offset at which every line starts
+    line_starts: Vec<usize>,
+}
+
+// a 1-based line and column, the column counts chars rather than bytes
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        Self { source, line_starts }
+    }
+
+    pub fn line_col(&self, offset: usize) -> LineCol {
+        let offset = offset.min(self.source.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+
+        let start = self.line_starts[line];
+        let col = self.source[start..offset].chars().count() + 1;
+
+        LineCol { line: line + 1, col }
+    }
+
+    // the start and end positions of a span
+    pub fn span_range(&self, span: Span) -> (LineCol, LineCol) {
+        (self.line_col(span.start), self.line_col(span.end))
+    }
+
+    // the span of the char at a `Loc`, whose row is 0-based and whose column is 1-based and counts
+    // chars, the span is empty past the end of a line
+    pub fn char_span(&self, loc: &Loc) -> Span {
+        let Some(&start) = self.line_starts.get(loc.row as usize) else {
+            return Span::new(self.source.len(), self.source.len());
+        };
+        let text = self.line_text(loc.row as usize + 1);
+
+        match text.char_indices().nth((loc.col as usize).saturating_sub(1)) {
+            Some((i, c)) => Span::new(start + i, start + i + c.len_utf8()),
+            None => Span::new(start + text.len(), start + text.len()),
+        }
+    }
+
+    // the text of a 1-based line, without its line break
+    pub fn line_text(&self, line: usize) -> &'a str {
+        let Some(&start) = self.line_starts.get(line.wrapping_sub(1)) else {
+            return "";
+        };
+        let end = self.line_starts.get(line).copied().unwrap_or(self.source.len());
+
+        self.source[start..end].trim_end_matches(['\n', '\r'])
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+}
+
+impl std::fmt::Display for LineCol {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.col)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::token::{lookup_keyword, Loc, Token, TokenKind};
+use crate::token::{lookup_keyword, Loc, Span, Token, TokenKind};
 
 pub struct Scanner<'a> {
     input: &'a str,  // input is now a string slice
@@ -29,10 +29,27 @@ impl<'a> Scanner<'a> {
 // implementation for private functions
 impl<'a> Scanner<'a> {
     pub fn next_token(&mut self) -> Token<'a> {
+        if let Some(unterminated) = self.skip_trivia() {
+            return unterminated;
+        }
+
+        // past the end, `position` keeps growing with every `advance`
+        let start = self.position.min(self.input.len());
+        let mut token = self.scan_token();
+        let end = self.position.min(self.input.len());
+
+        token.span = Span::new(start, end);
+        if token.text.is_empty() {
+            token.text = &self.input[start..end];
+        }
+
+        token
+    }
+
+    fn scan_token(&mut self) -> Token<'a> {
         let token: TokenKind;
-        let loc = self.get_loc();
         let content = "";
-        self.ignore_whitespace();
+        let loc = self.get_loc();
 
         match self.curr {
             '\0' => {
@@ -45,6 +62,9 @@ impl<'a> Scanner<'a> {
                 token = if self.peek() == '+' {
                     self.advance();
                     TokenKind::Inc
+                } else if self.peek() == '=' {
+                    self.advance();
+                    TokenKind::PlusAssign
                 } else {
                     TokenKind::Plus
                 };
@@ -56,15 +76,54 @@ impl<'a> Scanner<'a> {
                 } else if self.peek() == '-' {
                     self.advance();
                     TokenKind::Dec
+                } else if self.peek() == '=' {
+                    self.advance();
+                    TokenKind::MinAssign
                 } else {
                     TokenKind::Min
                 }
             }
             '*' => {
-                token = TokenKind::Mul;
+                token = if self.peek() == '*' {
+                    self.advance();
+                    TokenKind::Pow
+                } else if self.peek() == '=' {
+                    self.advance();
+                    TokenKind::MulAssign
+                } else {
+                    TokenKind::Mul
+                };
+            }
+            '%' => {
+                token = if self.peek() == '=' {
+                    self.advance();
+                    TokenKind::ModAssign
+                } else {
+                    TokenKind::Mod
+                };
+            }
+            '.' => {
+                if self.peek() == '.' {
+                    self.advance();
+                    if self.peek() == '=' {
+                        self.advance();
+                        token = TokenKind::RangeInclusive;
+                    } else {
+                        token = TokenKind::Range;
+                    }
+                } else {
+                    let text = &self.input[self.position..self.offset];
+                    self.advance();
+                    return Token::new(TokenKind::Illegal, loc, text);
+                }
             }
             '/' => {
-                token = TokenKind::Div;
+                token = if self.peek() == '=' {
+                    self.advance();
+                    TokenKind::DivAssign
+                } else {
+                    TokenKind::Div
+                };
             }
             '(' => {
                 token = TokenKind::LeftParen;
@@ -101,12 +160,20 @@ impl<'a> Scanner<'a> {
                 token = TokenKind::BNot;
             }
             '^' => {
-                token = TokenKind::Xor;
+                token = if self.peek() == '=' {
+                    self.advance();
+                    TokenKind::XorAssign
+                } else {
+                    TokenKind::Xor
+                };
             }
             '|' => {
                 if self.peek() == '|' {
                     self.advance();
                     token = TokenKind::Or;
+                } else if self.peek() == '=' {
+                    self.advance();
+                    token = TokenKind::BOrAssign;
                 } else {
                     token = TokenKind::BOr;
                 }
@@ -115,6 +182,9 @@ impl<'a> Scanner<'a> {
                 if self.peek() == '&' {
                     self.advance();
                     token = TokenKind::And;
+                } else if self.peek() == '=' {
+                    self.advance();
+                    token = TokenKind::BAndAssign;
                 } else {
                     token = TokenKind::BAnd;
                 }
@@ -122,7 +192,12 @@ impl<'a> Scanner<'a> {
             '>' => {
                 if self.peek() == '>' {
                     self.advance();
-                    token = TokenKind::RightSh;
+                    if self.peek() == '=' {
+                        self.advance();
+                        token = TokenKind::RightShAssign;
+                    } else {
+                        token = TokenKind::RightSh;
+                    }
                 } else if self.peek() == '=' {
                     self.advance();
                     token = TokenKind::GreaterOrEqu;
@@ -133,7 +208,12 @@ impl<'a> Scanner<'a> {
             '<' => {
                 if self.peek() == '<' {
                     self.advance();
-                    token = TokenKind::LeftSh;
+                    if self.peek() == '=' {
+                        self.advance();
+                        token = TokenKind::LeftShAssign;
+                    } else {
+                        token = TokenKind::LeftSh;
+                    }
                 } else if self.peek() == '=' {
                     self.advance();
                     token = TokenKind::LowerOrEqu;
@@ -153,10 +233,12 @@ impl<'a> Scanner<'a> {
                     } else {
                         return Token::new(lookup_keyword(id), loc, id);
                     }
-                } else if self.curr.is_numeric() {
+                } else if self.curr.is_ascii_digit() {
                     return self.get_number();
                 } else {
-                    return Token::new(TokenKind::Illegal, loc, &self.input[self.position..][..1]);
+                    let text = &self.input[self.position..self.offset];
+                    self.advance();
+                    return Token::new(TokenKind::Illegal, loc, text);
                 }
             }
         }
@@ -183,12 +265,74 @@ impl<'a> Scanner<'a> {
         }
     }
 
+    // eats up white-space and comments, an unterminated block comment is returned as a token
+    fn skip_trivia(&mut self) -> Option<Token<'a>> {
+        loop {
+            self.ignore_whitespace();
+
+            match (self.curr, self.peek()) {
+                ('/', '/') => self.skip_line_comment(),
+                ('/', '*') => {
+                    if let Some(unterminated) = self.skip_block_comment() {
+                        return Some(unterminated);
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    // skips a `// ...` comment up to (not including) the end of the line
+    fn skip_line_comment(&mut self) {
+        while self.curr != '\n' && self.curr != '\0' {
+            self.advance();
+        }
+    }
+
+    // skips a `/* ... */` comment, block comments may be nested
+    fn skip_block_comment(&mut self) -> Option<Token<'a>> {
+        let begin_pos = self.position;
+        let loc = self.get_loc();
+        let mut depth = 0;
+
+        loop {
+            match (self.curr, self.peek()) {
+                ('\0', _) => {
+                    let mut unterminated = Token::new(TokenKind::UnterminatedComment, loc, &self.input[begin_pos..][..2]);
+                    unterminated.span = Span::new(begin_pos, begin_pos + 2);
+                    return Some(unterminated);
+                }
+                ('/', '*') => {
+                    depth += 1;
+                    self.advance();
+                }
+                ('*', '/') => {
+                    depth -= 1;
+                    self.advance();
+
+                    if depth == 0 {
+                        self.advance();
+                        return None;
+                    }
+                }
+                ('\n', _) => {
+                    self.line += 1;
+                    self.col = 0;
+                }
+                _ => {}
+            }
+
+            self.advance();
+        }
+    }
+
     // moves to the next char
     fn advance(&mut self) {
         self.curr = self.peek();
 
         self.position = self.offset;
-        self.offset += 1;
+        // multi-byte chars must be skipped as a whole, slicing inside them panics
+        self.offset += self.curr.len_utf8();
         self.col += 1;
     }
 
@@ -201,21 +345,35 @@ impl<'a> Scanner<'a> {
         }
     }
 
+    // scans a string literal including its quotes, escapes are decoded by the parser
     fn get_string(&mut self) -> Token<'a> {
         let begin_pos = self.position;
         let loc = self.get_loc();
 
         self.advance(); // remove '"'
 
-        while self.peek() != '"' {
-            self.advance();
+        loop {
+            match self.curr {
+                '"' => break,
+                '\0' => return Token::new(TokenKind::UnterminatedString, loc, &self.input[begin_pos..self.position]),
+                '\\' => {
+                    // the escaped char can never end the string
+                    self.advance();
+                    if self.curr == '\0' {
+                        continue;
+                    }
+                }
+                _ => {}
+            }
 
-            if self.peek() == '\0' {
-                return Token::new(TokenKind::Illegal, loc, &self.input[begin_pos..self.position]);
+            if self.curr == '\n' {
+                self.line += 1;
+                self.col = 0;
             }
+            self.advance();
         }
 
-        self.advance();
+        self.advance(); // remove closing '"'
 
         Token::new(TokenKind::String, loc, &self.input[begin_pos..self.position])
     }
@@ -231,27 +389,66 @@ impl<'a> Scanner<'a> {
         &self.input[begin_pos..self.position]
     }
 
+    // scans a decimal (`1_000`), hex (`0xFF`), binary (`0b1010`), octal (`0o17`) or
+    // floating point (`1.5`, `1.5e-3`) literal, malformed literals are returned as invalid numbers
     fn get_number(&mut self) -> Token<'a> {
         let begin_pos = self.position;
         let loc = self.get_loc();
-        let mut count = 0;
-        let mut is_floating = false;
 
-        while self.curr.is_ascii_digit() || self.curr == '.' {
-            if self.curr == '.' {
-                if is_floating {
-                    return Token::new(TokenKind::Illegal, loc, &self.input[begin_pos..][..count]);
+        let radix = match (self.curr, self.peek()) {
+            ('0', 'x') => 16,
+            ('0', 'b') => 2,
+            ('0', 'o') => 8,
+            _ => 10,
+        };
+
+        let mut kind = TokenKind::Integer;
+        if radix != 10 {
+            self.advance();
+            self.advance();
+
+            if self.eat_digits(radix) == 0 {
+                kind = TokenKind::InvalidNumber;
+            }
+        } else {
+            self.eat_digits(10);
+
+            // `..` belongs to a range, not to the number (e.g. `0..10`)
+            if self.curr == '.' && self.peek() != '.' {
+                self.advance();
+                self.eat_digits(10);
+                kind = TokenKind::Double;
+            }
+
+            if matches!(self.curr, 'e' | 'E') {
+                self.advance();
+                if matches!(self.curr, '+' | '-') {
+                    self.advance();
                 }
-                is_floating = true;
+
+                kind = if self.eat_digits(10) == 0 { TokenKind::InvalidNumber } else { TokenKind::Double };
             }
-            count += 1;
+        }
+
+        // a literal running into letters, digits or another fraction is malformed (e.g. `0b102`, `1.2.3`)
+        while self.curr.is_alphanumeric() || self.curr == '_' || (self.curr == '.' && self.peek().is_ascii_digit()) {
+            kind = TokenKind::InvalidNumber;
             self.advance();
         }
 
-        if is_floating {
-            Token::new(TokenKind::Double, loc, &self.input[begin_pos..][..count])
-        } else {
-            Token::new(TokenKind::Integer, loc, &self.input[begin_pos..][..count])
+        Token::new(kind, loc, &self.input[begin_pos..self.position])
+    }
+
+    // eats digits of the given radix along with `_` separators, returns how many digits were found
+    fn eat_digits(&mut self, radix: u32) -> usize {
+        let mut digits = 0;
+        while self.curr.is_digit(radix) || self.curr == '_' {
+            if self.curr != '_' {
+                digits += 1;
+            }
+            self.advance();
         }
+
+        digits
     }
 }
@@ -10,10 +10,43 @@ impl std::fmt::Display for Loc {
     }
 }
 
+// a range of bytes in the source, `end` is exclusive
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    // the smallest span covering both spans
+    pub fn to(&self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Token<'a> {
     pub kind: TokenKind,
     pub loc: Loc,
+    pub span: Span,
     pub text: &'a str
 }
 
@@ -22,6 +55,7 @@ impl<'a> Token<'a> {
         Self {
             kind,
             loc,
+            span: Span::default(),
             text,
         }
     }
@@ -30,6 +64,7 @@ impl<'a> Token<'a> {
         Self {
             kind,
             loc: Loc { row: 0, col: 0 },
+            span: Span::default(),
             text: "",
         }
     }
@@ -39,7 +74,8 @@ impl<'a> Token<'a> {
     }
 
     pub fn is_binary(&self) -> bool {
-        matches!(self.kind, TokenKind::Plus | TokenKind::Min | TokenKind::Mul | TokenKind::Div | TokenKind::Assign | TokenKind::And | TokenKind::Or | TokenKind::Equals | TokenKind::NotEquals | TokenKind::Greater | TokenKind::GreaterOrEqu | TokenKind::Lower | TokenKind::LowerOrEqu | TokenKind::BAnd | TokenKind::BOr | TokenKind::Xor | TokenKind::RightSh | TokenKind::LeftSh)
+        matches!(self.kind, TokenKind::Plus | TokenKind::Min | TokenKind::Mul | TokenKind::Div | TokenKind::Mod | TokenKind::Pow | TokenKind::Assign | TokenKind::And | TokenKind::Or | TokenKind::Equals | TokenKind::NotEquals | TokenKind::Greater | TokenKind::GreaterOrEqu | TokenKind::Lower | TokenKind::LowerOrEqu | TokenKind::BAnd | TokenKind::BOr | TokenKind::Xor | TokenKind::RightSh | TokenKind::LeftSh
+            | TokenKind::PlusAssign | TokenKind::MinAssign | TokenKind::MulAssign | TokenKind::DivAssign | TokenKind::ModAssign | TokenKind::BAndAssign | TokenKind::BOrAssign | TokenKind::XorAssign | TokenKind::RightShAssign | TokenKind::LeftShAssign)
     }
 }
 
@@ -65,15 +101,33 @@ pub enum TokenKind {
     // points to the return type of a function (.e.g. fn foo() -> int)
     ReturnTypeArrow,
 
+    // ranges (.e.g. 0..10, 0..=10)
+    Range,
+    RangeInclusive,
+
     // operators
     Plus,
     Mul,
     Div,
     Min,
+    Mod,
+    Pow,
     Inc,
     Dec,
     Assign,
 
+    // compound assignments (.e.g. a += 1)
+    PlusAssign,
+    MinAssign,
+    MulAssign,
+    DivAssign,
+    ModAssign,
+    BAndAssign,
+    BOrAssign,
+    XorAssign,
+    RightShAssign,
+    LeftShAssign,
+
     And,
     Not,
     Or,
@@ -98,6 +152,7 @@ pub enum TokenKind {
     Elif,
     Else,
     For,
+    In,
     While,
 
     // do-while stuff
@@ -113,17 +168,35 @@ pub enum TokenKind {
     Return,
 
     Illegal,
+    // a `/* ...` block comment the input ends inside of
+    UnterminatedComment,
+    // a string literal the input ends inside of
+    UnterminatedString,
+    // a numeric literal in none of the accepted forms (e.g. `0b102`, `1e`)
+    InvalidNumber,
     EOF,
 }
 
 impl std::fmt::Display for Token<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self.kind {
+            // tokens whose text varies are shown as written
+            TokenKind::Identifier | TokenKind::Integer | TokenKind::Double | TokenKind::String | TokenKind::Boolean | TokenKind::Illegal | TokenKind::InvalidNumber => write!(f, "{}", self.text),
+            _ => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl std::fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
             // Arithmetic operators
             TokenKind::Min => write!(f, "-"),
             TokenKind::Plus => write!(f, "+"),
             TokenKind::Mul => write!(f, "*"),
             TokenKind::Div => write!(f, "/"),
+            TokenKind::Mod => write!(f, "%"),
+            TokenKind::Pow => write!(f, "**"),
             TokenKind::Dec => write!(f, "--"),
             TokenKind::Inc => write!(f, "++"),
             
@@ -138,10 +211,20 @@ impl std::fmt::Display for Token<'_> {
             TokenKind::NotEquals => write!(f, "!="),
             TokenKind::Or => write!(f, "||"),
             
-            TokenKind::Identifier => write!(f, "{}", self.text),
+            TokenKind::Identifier => write!(f, "identifier"),
             
             // punctuation
             TokenKind::Assign => write!(f, "="),
+            TokenKind::PlusAssign => write!(f, "+="),
+            TokenKind::MinAssign => write!(f, "-="),
+            TokenKind::MulAssign => write!(f, "*="),
+            TokenKind::DivAssign => write!(f, "/="),
+            TokenKind::ModAssign => write!(f, "%="),
+            TokenKind::BAndAssign => write!(f, "&="),
+            TokenKind::BOrAssign => write!(f, "|="),
+            TokenKind::XorAssign => write!(f, "^="),
+            TokenKind::RightShAssign => write!(f, ">>="),
+            TokenKind::LeftShAssign => write!(f, "<<="),
             TokenKind::LeftBrace => write!(f, "{{"),
             TokenKind::RightBrace => write!(f, "}}"),
             TokenKind::LeftParen => write!(f, "("),
@@ -149,6 +232,8 @@ impl std::fmt::Display for Token<'_> {
             TokenKind::Semicolon => write!(f, ";"),
             TokenKind::ReturnTypeArrow => write!(f, "->"),
             TokenKind::Comma => write!(f, ","),
+            TokenKind::Range => write!(f, ".."),
+            TokenKind::RangeInclusive => write!(f, "..="),
 
             // bitwise operators
             TokenKind::BAnd => write!(f, "&"),
@@ -159,10 +244,10 @@ impl std::fmt::Display for Token<'_> {
             TokenKind::Xor => write!(f, "^"),
             
             // data values
-            TokenKind::Double => write!(f, "{}", self.text),
-            TokenKind::Integer => write!(f, "{}", self.text),
-            TokenKind::String => write!(f, "{}", self.text),
-            TokenKind::Boolean => write!(f, "{}", self.text),
+            TokenKind::Double => write!(f, "double"),
+            TokenKind::Integer => write!(f, "integer"),
+            TokenKind::String => write!(f, "string"),
+            TokenKind::Boolean => write!(f, "boolean"),
 
             // keywords
             TokenKind::Break => write!(f, "break"),
@@ -170,8 +255,9 @@ impl std::fmt::Display for Token<'_> {
             TokenKind::Const => write!(f, "const"),
             TokenKind::Else => write!(f, "else"),
             TokenKind::Elif => write!(f, "elif"),
-            TokenKind::EOF => write!(f, "EOF"),
+            TokenKind::EOF => write!(f, "end of file"),
             TokenKind::For => write!(f, "for"),
+            TokenKind::In => write!(f, "in"),
             TokenKind::Fn => write!(f, "fn"),
             TokenKind::Return => write!(f, "return"),
             TokenKind::If => write!(f, "if"),
@@ -181,7 +267,10 @@ impl std::fmt::Display for Token<'_> {
             TokenKind::While => write!(f, "while"),
             TokenKind::Loop => write!(f, "loop"),
 
-            TokenKind::Illegal => write!(f, "{}", self.text),
+            TokenKind::Illegal => write!(f, "illegal token"),
+            TokenKind::UnterminatedComment => write!(f, "unterminated comment"),
+            TokenKind::UnterminatedString => write!(f, "unterminated string"),
+            TokenKind::InvalidNumber => write!(f, "invalid number"),
 
         }
     }
@@ -200,6 +289,7 @@ pub fn lookup_keyword(identifier: &str) -> TokenKind {
         "return" => TokenKind::Return,
 
         "for" => TokenKind::For,
+        "in" => TokenKind::In,
 
         "loop" => TokenKind::Loop,
         "unless" => TokenKind::Unless,
@@ -0,0 +1,62 @@
+use xta_lexer::{scanner::Scanner, token::{Loc, Span, Token, TokenKind}};
+
+fn tokens(source: &str) -> Vec<Token<'_>> {
+    let mut scanner = Scanner::new(source);
+    let mut tokens = Vec::new();
+
+    loop {
+        let token = scanner.next_token();
+        if token.kind == TokenKind::EOF {
+            return tokens;
+        }
+        tokens.push(token);
+    }
+}
+
+fn texts(source: &str) -> Vec<&str> {
+    tokens(source).iter().map(|token| token.text).collect()
+}
+
+#[test]
+fn comments_are_skipped() {
+    assert_eq!(texts("a // b c\nd"), ["a", "d"]);
+    assert_eq!(texts("a // at the end"), ["a"]);
+    assert_eq!(texts("a /* b */ c"), ["a", "c"]);
+    assert_eq!(texts("a/**/b"), ["a", "b"]);
+    assert_eq!(texts("a / b"), ["a", "/", "b"]);
+    assert_eq!(texts("/* a */ // b\n/* c */"), Vec::<&str>::new());
+}
+
+#[test]
+fn block_comments_nest() {
+    assert_eq!(texts("a /* b /* c */ d */ e"), ["a", "e"]);
+    assert_eq!(texts("a /* /* /* */ */ */ e"), ["a", "e"]);
+    // a line comment inside a block comment does not hide its end
+    assert_eq!(texts("a /* // */ e"), ["a", "e"]);
+}
+
+#[test]
+fn locs_are_tracked_across_comments() {
+    let found = tokens("a // one\n  b /* two\nthree\n */ c");
+
+    assert_eq!(found[0].loc, Loc { row: 0, col: 1 });
+    assert_eq!(found[1].loc, Loc { row: 1, col: 3 });
+    assert_eq!(found[2].loc, Loc { row: 3, col: 5 });
+    assert_eq!(found[2].span, Span::new(30, 31));
+}
+
+#[test]
+fn unterminated_block_comment_is_a_token() {
+    let found = tokens("a\n  /* b /* c */ d");
+
+    assert_eq!(found.len(), 2, "found {:?}", found);
+    assert_eq!(found[1].kind, TokenKind::UnterminatedComment);
+    // the token marks the opening `/*`
+    assert_eq!(found[1].text, "/*");
+    assert_eq!(found[1].loc, Loc { row: 1, col: 3 });
+    assert_eq!(found[1].span, Span::new(4, 6));
+
+    let found = tokens("/*/");
+    assert_eq!(found.len(), 1, "found {:?}", found);
+    assert_eq!(found[0].kind, TokenKind::UnterminatedComment);
+}
@@ -0,0 +1,63 @@
+use xta_lexer::{line_index::{LineCol, LineIndex}, token::{Loc, Span}};
+
+#[test]
+fn offsets_map_to_lines_and_columns() {
+    let index = LineIndex::new("let a;\nlet b;\n\nlet c;");
+
+    assert_eq!(index.line_col(0), LineCol { line: 1, col: 1 });
+    assert_eq!(index.line_col(4), LineCol { line: 1, col: 5 });
+    // the line break still belongs to the line it ends
+    assert_eq!(index.line_col(6), LineCol { line: 1, col: 7 });
+    assert_eq!(index.line_col(7), LineCol { line: 2, col: 1 });
+    assert_eq!(index.line_col(14), LineCol { line: 3, col: 1 });
+    assert_eq!(index.line_col(19), LineCol { line: 4, col: 5 });
+    assert_eq!(index.line_count(), 4);
+}
+
+#[test]
+fn columns_count_chars_rather_than_bytes() {
+    let source = "let é = \"ü\";\n\tlet ñ;";
+    let index = LineIndex::new(source);
+
+    assert_eq!(index.line_col(source.find('=').unwrap()), LineCol { line: 1, col: 7 });
+    assert_eq!(index.line_col(source.find(';').unwrap()), LineCol { line: 1, col: 12 });
+    // a tab is a single column
+    assert_eq!(index.line_col(source.find('ñ').unwrap()), LineCol { line: 2, col: 6 });
+}
+
+#[test]
+fn spans_and_offsets_past_the_end() {
+    let index = LineIndex::new("a +\n  b");
+
+    assert_eq!(
+        index.span_range(Span::new(0, 7)),
+        (LineCol { line: 1, col: 1 }, LineCol { line: 2, col: 4 })
+    );
+    assert_eq!(index.line_col(100), LineCol { line: 2, col: 4 });
+    assert_eq!(index.line_col(100).to_string(), "2:4");
+}
+
+#[test]
+fn line_text_drops_the_line_break() {
+    let index = LineIndex::new("fn main() {\r\n    print(1);\r\n}\n");
+
+    assert_eq!(index.line_text(1), "fn main() {");
+    assert_eq!(index.line_text(2), "    print(1);");
+    assert_eq!(index.line_text(3), "}");
+    assert_eq!(index.line_text(4), "");
+    assert_eq!(index.line_text(0), "");
+    assert_eq!(index.line_text(9), "");
+}
+
+#[test]
+fn locs_map_to_the_span_of_their_char() {
+    let source = "let a;\nlet é = a / é;\n";
+    let index = LineIndex::new(source);
+
+    // rows start at 0 and columns at 1
+    assert_eq!(index.char_span(&Loc { row: 0, col: 1 }), Span::new(0, 1));
+    assert_eq!(index.char_span(&Loc { row: 1, col: 5 }), Span::new(11, 13));
+    assert_eq!(index.char_span(&Loc { row: 1, col: 13 }), Span::new(20, 22));
+    assert_eq!(index.char_span(&Loc { row: 1, col: 40 }), Span::new(23, 23));
+    assert_eq!(index.char_span(&Loc { row: 9, col: 1 }), Span::new(source.len(), source.len()));
+}
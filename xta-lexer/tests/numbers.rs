@@ -0,0 +1,104 @@
+use xta_lexer::{scanner::Scanner, token::TokenKind};
+
+fn tokens(source: &str) -> Vec<(TokenKind, &str)> {
+    let mut scanner = Scanner::new(source);
+    let mut tokens = Vec::new();
+
+    loop {
+        let token = scanner.next_token();
+        if token.kind == TokenKind::EOF {
+            return tokens;
+        }
+        tokens.push((token.kind, token.text));
+    }
+}
+
+// every literal that has to scan into a single token of the given kind
+const SINGLE: &[(&str, TokenKind)] = &[
+    // decimal
+    ("0", TokenKind::Integer),
+    ("42", TokenKind::Integer),
+    ("1_000_000", TokenKind::Integer),
+    ("1__0", TokenKind::Integer),
+    ("1_", TokenKind::Integer),
+
+    // other radixes
+    ("0xFF", TokenKind::Integer),
+    ("0xdead_beef", TokenKind::Integer),
+    ("0x_FF", TokenKind::Integer),
+    ("0b1010", TokenKind::Integer),
+    ("0b1_0", TokenKind::Integer),
+    ("0o17", TokenKind::Integer),
+
+    // floating point
+    ("1.5", TokenKind::Double),
+    ("1.", TokenKind::Double),
+    ("1.5e-3", TokenKind::Double),
+    ("1e3", TokenKind::Double),
+    ("1E+3", TokenKind::Double),
+    ("1.e3", TokenKind::Double),
+    ("1_0.2_5", TokenKind::Double),
+
+    // malformed
+    ("0x", TokenKind::InvalidNumber),
+    ("0b", TokenKind::InvalidNumber),
+    ("0x_", TokenKind::InvalidNumber),
+    ("0b102", TokenKind::InvalidNumber),
+    ("0o8", TokenKind::InvalidNumber),
+    ("0xFG", TokenKind::InvalidNumber),
+    ("1e", TokenKind::InvalidNumber),
+    ("1e+", TokenKind::InvalidNumber),
+    ("1.5e", TokenKind::InvalidNumber),
+    ("1.2.3", TokenKind::InvalidNumber),
+    ("12abc", TokenKind::InvalidNumber),
+    ("1.5x", TokenKind::InvalidNumber),
+    ("1é", TokenKind::InvalidNumber),
+];
+
+#[test]
+fn literals_scan_into_one_token() {
+    let mut failures = Vec::new();
+
+    for (source, kind) in SINGLE {
+        let found = tokens(source);
+        if found != [(kind.clone(), *source)] {
+            failures.push(format!("`{source}`: expected a single {kind:?}, found {found:?}"));
+        }
+    }
+
+    assert!(failures.is_empty(), "{} case(s) failed:\n{}", failures.len(), failures.join("\n"));
+}
+
+#[test]
+fn ranges_are_not_part_of_the_number() {
+    assert_eq!(
+        tokens("1..2"),
+        [(TokenKind::Integer, "1"), (TokenKind::Range, ".."), (TokenKind::Integer, "2")]
+    );
+    assert_eq!(
+        tokens("0..=0xF"),
+        [(TokenKind::Integer, "0"), (TokenKind::RangeInclusive, "..="), (TokenKind::Integer, "0xF")]
+    );
+    assert_eq!(
+        tokens("1.5..2"),
+        [(TokenKind::Double, "1.5"), (TokenKind::Range, ".."), (TokenKind::Integer, "2")]
+    );
+}
+
+#[test]
+fn numbers_end_at_operators_and_whitespace() {
+    assert_eq!(
+        tokens("1+2 -3e2*0x1F"),
+        [
+            (TokenKind::Integer, "1"),
+            (TokenKind::Plus, "+"),
+            (TokenKind::Integer, "2"),
+            (TokenKind::Min, "-"),
+            (TokenKind::Double, "3e2"),
+            (TokenKind::Mul, "*"),
+            (TokenKind::Integer, "0x1F"),
+        ]
+    );
+    // the sign of an exponent belongs to it, a second one does not
+    assert_eq!(tokens("1e-+2"), [(TokenKind::InvalidNumber, "1e-"), (TokenKind::Plus, "+"), (TokenKind::Integer, "2")]);
+}
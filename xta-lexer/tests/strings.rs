@@ -0,0 +1,56 @@
+use xta_lexer::{scanner::Scanner, token::{Loc, Span, Token, TokenKind}};
+
+fn tokens(source: &str) -> Vec<Token<'_>> {
+    let mut scanner = Scanner::new(source);
+    let mut tokens = Vec::new();
+
+    loop {
+        let token = scanner.next_token();
+        if token.kind == TokenKind::EOF {
+            return tokens;
+        }
+        tokens.push(token);
+    }
+}
+
+#[test]
+fn strings_keep_their_quotes_and_escapes() {
+    let source = r#"a "b \"c\" \\" d"#;
+    let found = tokens(source);
+
+    assert_eq!(found.len(), 3, "found {:?}", found);
+    assert_eq!(found[1].kind, TokenKind::String);
+    assert_eq!(found[1].text, r#""b \"c\" \\""#);
+    assert_eq!(found[1].span, Span::new(2, 14));
+    assert_eq!(found[2].text, "d");
+}
+
+#[test]
+fn locs_are_tracked_across_strings() {
+    let found = tokens("\"one\ntwo\\\nthree\" x");
+
+    assert_eq!(found[0].kind, TokenKind::String);
+    assert_eq!(found[0].loc, Loc { row: 0, col: 1 });
+    assert_eq!(found[1].text, "x");
+    assert_eq!(found[1].loc, Loc { row: 2, col: 8 });
+}
+
+#[test]
+fn unterminated_string_is_a_token() {
+    let found = tokens("a\n  \"open\nstring");
+
+    assert_eq!(found.len(), 2, "found {:?}", found);
+    assert_eq!(found[1].kind, TokenKind::UnterminatedString);
+    assert_eq!(found[1].text, "\"open\nstring");
+    assert_eq!(found[1].loc, Loc { row: 1, col: 3 });
+    assert_eq!(found[1].span, Span::new(4, 16));
+
+    // an escaped quote cannot end the string
+    let found = tokens(r#""a\""#);
+    assert_eq!(found.len(), 1, "found {:?}", found);
+    assert_eq!(found[0].kind, TokenKind::UnterminatedString);
+
+    let found = tokens(r#""a\"#);
+    assert_eq!(found.len(), 1, "found {:?}", found);
+    assert_eq!(found[0].kind, TokenKind::UnterminatedString);
+}
@@ -1,4 +1,6 @@
-use xta_lexer::token::Loc;
+use std::borrow::Cow;
+
+use xta_lexer::token::{Loc, Span};
 
 pub type Block<'a> = Vec<Stmt<'a>>;
 
@@ -6,9 +8,13 @@ pub type Block<'a> = Vec<Stmt<'a>>;
 pub enum UnaryOpType {
     Neg,
     Not,
+    // `++x` and `--x`, evaluate to the updated value
     Inc,
     Dec,
     BitNot,
+    // `x++` and `x--`, evaluate to the value before the update
+    PostInc,
+    PostDec,
 }
 
 
@@ -18,6 +24,8 @@ pub enum BinaryOpType {
     Sub,
     Mul,
     Div,
+    Mod,
+    Pow,
 
     And,
     Or,
@@ -36,6 +44,25 @@ pub enum BinaryOpType {
     RShift,
 
     Assign,
+    // `a += b` stores `a + b` into `a`, and so on
+    AddAssign,
+    SubAssign,
+    MulAssign,
+    DivAssign,
+    ModAssign,
+    BitAndAssign,
+    BitOrAssign,
+    BitXorAssign,
+    LShiftAssign,
+    RShiftAssign,
+}
+
+// how a chain of operators sharing a precedence groups:
+// `a - b - c` is `(a - b) - c`, while `a ** b ** c` is `a ** (b ** c)`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Assoc {
+    Left,
+    Right,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -44,6 +71,11 @@ pub enum Stmt<'a> {
     FunctionDecl(FunctionDeclStmt<'a>),
     If(IfStmt<'a>),
     Return(ReturnStmt<'a>),
+    While(WhileStmt<'a>),
+    For(ForStmt<'a>),
+    Loop(LoopStmt<'a>),
+    Break(BreakStmt),
+    Continue(ContinueStmt),
     Expr(Expr<'a>),
 }
 
@@ -54,6 +86,7 @@ pub enum Expr<'a> {
     Literal(LiteralExpr<'a>),
     Identifier(IdentifierExpr<'a>),
     Call(CallExpr<'a>),
+    Error(ErrorExpr),
 }
 
 
@@ -64,25 +97,36 @@ pub struct CallExpr<'a> {
     pub name: &'a str,
     pub args: Vec<Expr<'a>>,
     pub loc: Loc,
+    pub span: Span,
+}
+
+// stands in for code the parser failed on, the error itself is already reported.
+// a statement that failed as a whole is kept as `Stmt::Expr(Expr::Error(..))`
+#[derive(Debug, PartialEq, Clone)]
+pub struct ErrorExpr {
+    pub loc: Loc,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct IdentifierExpr<'a> {
     pub name: &'a str,
-    pub loc: Loc
+    pub loc: Loc,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct LiteralExpr<'a> {
     pub value: Literal<'a>,
     pub loc: Loc,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Literal<'a> {
     Integer(i64),
     Double(f64),
-    String(&'a str),
+    String(Cow<'a, str>),
     Boolean(bool),
     None,
 }
@@ -94,7 +138,10 @@ pub struct BinaryExpr<'a> {
     pub left: Box<Expr<'a>>,
     pub right: Box<Expr<'a>>,
     pub operator: BinaryOpType,
+    // `loc` and `span` cover the whole expression, from the left operand through the right one
     pub loc: Loc,
+    pub span: Span,
+    pub op_span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -102,19 +149,25 @@ pub struct UnaryExpr<'a> {
     pub operand: Box<Expr<'a>>,
     pub operator: UnaryOpType,
     pub loc: Loc,
+    pub span: Span,
 }
 
 // custom statements
 #[derive(Debug, PartialEq, Clone)]
 pub struct VarDeclStmt<'a> {
     pub name: &'a str,
+    pub var_type: Option<&'a str>,
     pub value: Option<Expr<'a>>,
     pub is_const: bool,
+    pub loc: Loc,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ReturnStmt<'a> {
     pub value: Option<Expr<'a>>,
+    pub loc: Loc,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -123,44 +176,173 @@ pub struct IfStmt<'a> {
     pub then: Block<'a>,
     pub elif_branch: Vec<ElifStmt<'a>>,
     pub else_branch: Option<Block<'a>>,
+    pub loc: Loc,
+    pub span: Span,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct WhileStmt<'a> {
+    pub condition: Expr<'a>,
+    pub body: Block<'a>,
+    pub loc: Loc,
+    pub span: Span,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForStmt<'a> {
+    pub var: IdentifierExpr<'a>,
+    pub start: Expr<'a>,
+    pub end: Expr<'a>,
+    pub inclusive: bool,
+    pub body: Block<'a>,
+    pub loc: Loc,
+    pub span: Span,
+}
+
+// `loop { ... }` runs until a `break`, `loop { ... } unless cond;` also stops
+// once `cond` holds after an iteration
+#[derive(Debug, PartialEq, Clone)]
+pub struct LoopStmt<'a> {
+    pub body: Block<'a>,
+    pub unless: Option<Expr<'a>>,
+    pub loc: Loc,
+    pub span: Span,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BreakStmt {
+    pub loc: Loc,
+    pub span: Span,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ContinueStmt {
+    pub loc: Loc,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ElifStmt<'a> {
     pub condition: Expr<'a>,
     pub then: Block<'a>,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct FunctionDeclStmt<'a> {
     pub name: &'a str,
+    // the span of the name alone, `span` covers the whole function including its body
+    pub name_span: Span,
     pub params: Vec<Param<'a>>,
     pub return_type: Option<&'a str>,
     pub body: Block<'a>,
+    pub loc: Loc,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Param<'a> {
     pub name: &'a str,
     pub param_type: &'a str,
+    pub loc: Loc,
+    pub span: Span,
 }
 
 // Implementations:
 
+// The operator table, from the loosest binding to the tightest. The parser climbs these
+// precedences, so this is the one place that decides how an expression groups:
+//
+//   0   = += -= *= /= %= &= |= ^= <<= >>=   right
+//   1   ||                                   left
+//   2   &&                                   left
+//   3   == !=                                left
+//   4   < > <= >=                            left
+//   5   |                                    left
+//   6   ^                                    left
+//   7   &                                    left
+//   8   << >>                                left
+//   9   + -                                  left
+//   10  * / %                                left
+//   11  prefix - ! ~ ++ --                   right
+//   12  **                                   right
+//   13  postfix ++ --                        left
+impl UnaryOpType {
+    pub fn binding(&self) -> (u8, Assoc) {
+        match self {
+            UnaryOpType::Neg | UnaryOpType::Not | UnaryOpType::BitNot | UnaryOpType::Inc | UnaryOpType::Dec => (11, Assoc::Right),
+            UnaryOpType::PostInc | UnaryOpType::PostDec => (13, Assoc::Left),
+        }
+    }
+
+    pub fn prec(&self) -> u8 {
+        self.binding().0
+    }
+
+    pub fn assoc(&self) -> Assoc {
+        self.binding().1
+    }
+
+    // whether the operator writes back to its operand
+    pub fn is_update(&self) -> bool {
+        matches!(self, UnaryOpType::Inc | UnaryOpType::Dec | UnaryOpType::PostInc | UnaryOpType::PostDec)
+    }
+}
+
 impl BinaryOpType {
+    pub fn binding(&self) -> (u8, Assoc) {
+        match self {
+            BinaryOpType::Assign
+            | BinaryOpType::AddAssign
+            | BinaryOpType::SubAssign
+            | BinaryOpType::MulAssign
+            | BinaryOpType::DivAssign
+            | BinaryOpType::ModAssign
+            | BinaryOpType::BitAndAssign
+            | BinaryOpType::BitOrAssign
+            | BinaryOpType::BitXorAssign
+            | BinaryOpType::LShiftAssign
+            | BinaryOpType::RShiftAssign => (0, Assoc::Right),
+            BinaryOpType::Or => (1, Assoc::Left),
+            BinaryOpType::And => (2, Assoc::Left),
+            BinaryOpType::Eq | BinaryOpType::Neq => (3, Assoc::Left),
+            BinaryOpType::Smaller | BinaryOpType::Greater | BinaryOpType::SmallerEq | BinaryOpType::GreaterEq => (4, Assoc::Left),
+            BinaryOpType::BitOr => (5, Assoc::Left),
+            BinaryOpType::BitXor => (6, Assoc::Left),
+            BinaryOpType::BitAnd => (7, Assoc::Left),
+            BinaryOpType::LShift | BinaryOpType::RShift => (8, Assoc::Left),
+            BinaryOpType::Add | BinaryOpType::Sub => (9, Assoc::Left),
+            BinaryOpType::Mul | BinaryOpType::Div | BinaryOpType::Mod => (10, Assoc::Left),
+            BinaryOpType::Pow => (12, Assoc::Right),
+        }
+    }
+
     pub fn prec(&self) -> u8 {
+        self.binding().0
+    }
+
+    pub fn assoc(&self) -> Assoc {
+        self.binding().1
+    }
+
+    pub fn is_assignment(&self) -> bool {
+        *self == BinaryOpType::Assign || self.compound_base().is_some()
+    }
+
+    // the operator a compound assignment applies before storing, e.g. `+` for `+=`
+    pub fn compound_base(&self) -> Option<BinaryOpType> {
         match self {
-            BinaryOpType::Assign => 0,
-            BinaryOpType::Or => 1,
-            BinaryOpType::And => 2,
-            BinaryOpType::Smaller | BinaryOpType::Greater | BinaryOpType::SmallerEq | BinaryOpType::GreaterEq => 3,
-            BinaryOpType::Eq | BinaryOpType::Neq => 4,
-            BinaryOpType::BitOr => 5,
-            BinaryOpType::BitXor => 6,
-            BinaryOpType::BitAnd => 7,
-            BinaryOpType::LShift | BinaryOpType::RShift => 8,
-            BinaryOpType::Add | BinaryOpType::Sub => 9,
-            BinaryOpType::Mul | BinaryOpType::Div => 10,
+            BinaryOpType::AddAssign => Some(BinaryOpType::Add),
+            BinaryOpType::SubAssign => Some(BinaryOpType::Sub),
+            BinaryOpType::MulAssign => Some(BinaryOpType::Mul),
+            BinaryOpType::DivAssign => Some(BinaryOpType::Div),
+            BinaryOpType::ModAssign => Some(BinaryOpType::Mod),
+            BinaryOpType::BitAndAssign => Some(BinaryOpType::BitAnd),
+            BinaryOpType::BitOrAssign => Some(BinaryOpType::BitOr),
+            BinaryOpType::BitXorAssign => Some(BinaryOpType::BitXor),
+            BinaryOpType::LShiftAssign => Some(BinaryOpType::LShift),
+            BinaryOpType::RShiftAssign => Some(BinaryOpType::RShift),
+            _ => None,
         }
     }
 
@@ -177,6 +359,38 @@ impl BinaryOpType {
     }
 }
 
+impl Stmt<'_> {
+    pub fn loc(&self) -> Loc {
+        match self {
+            Stmt::VarDecl(stmt) => stmt.loc.clone(),
+            Stmt::FunctionDecl(stmt) => stmt.loc.clone(),
+            Stmt::If(stmt) => stmt.loc.clone(),
+            Stmt::Return(stmt) => stmt.loc.clone(),
+            Stmt::While(stmt) => stmt.loc.clone(),
+            Stmt::For(stmt) => stmt.loc.clone(),
+            Stmt::Loop(stmt) => stmt.loc.clone(),
+            Stmt::Break(stmt) => stmt.loc.clone(),
+            Stmt::Continue(stmt) => stmt.loc.clone(),
+            Stmt::Expr(expr) => expr.loc(),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Stmt::VarDecl(stmt) => stmt.span,
+            Stmt::FunctionDecl(stmt) => stmt.span,
+            Stmt::If(stmt) => stmt.span,
+            Stmt::Return(stmt) => stmt.span,
+            Stmt::While(stmt) => stmt.span,
+            Stmt::For(stmt) => stmt.span,
+            Stmt::Loop(stmt) => stmt.span,
+            Stmt::Break(stmt) => stmt.span,
+            Stmt::Continue(stmt) => stmt.span,
+            Stmt::Expr(expr) => expr.span(),
+        }
+    }
+}
+
 impl Expr<'_> {
     pub fn loc(&self) -> Loc {
         match self {
@@ -185,6 +399,67 @@ impl Expr<'_> {
             Expr::Literal(expr) => expr.loc.clone(),
             Expr::Identifier(expr) => expr.loc.clone(),
             Expr::Call(expr) => expr.loc.clone(),
+            Expr::Error(expr) => expr.loc.clone(),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Expr::Binary(expr) => expr.span,
+            Expr::Unary(expr) => expr.span,
+            Expr::Literal(expr) => expr.span,
+            Expr::Identifier(expr) => expr.span,
+            Expr::Call(expr) => expr.span,
+            Expr::Error(expr) => expr.span,
+        }
+    }
+}
+
+impl std::fmt::Display for UnaryOpType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UnaryOpType::Neg => write!(f, "-"),
+            UnaryOpType::Not => write!(f, "!"),
+            UnaryOpType::Inc | UnaryOpType::PostInc => write!(f, "++"),
+            UnaryOpType::Dec | UnaryOpType::PostDec => write!(f, "--"),
+            UnaryOpType::BitNot => write!(f, "~"),
+        }
+    }
+}
+
+impl std::fmt::Display for BinaryOpType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BinaryOpType::Add => write!(f, "+"),
+            BinaryOpType::Sub => write!(f, "-"),
+            BinaryOpType::Mul => write!(f, "*"),
+            BinaryOpType::Div => write!(f, "/"),
+            BinaryOpType::Mod => write!(f, "%"),
+            BinaryOpType::Pow => write!(f, "**"),
+            BinaryOpType::And => write!(f, "&&"),
+            BinaryOpType::Or => write!(f, "||"),
+            BinaryOpType::Eq => write!(f, "=="),
+            BinaryOpType::Neq => write!(f, "!="),
+            BinaryOpType::Smaller => write!(f, "<"),
+            BinaryOpType::Greater => write!(f, ">"),
+            BinaryOpType::SmallerEq => write!(f, "<="),
+            BinaryOpType::GreaterEq => write!(f, ">="),
+            BinaryOpType::BitAnd => write!(f, "&"),
+            BinaryOpType::BitOr => write!(f, "|"),
+            BinaryOpType::BitXor => write!(f, "^"),
+            BinaryOpType::LShift => write!(f, "<<"),
+            BinaryOpType::RShift => write!(f, ">>"),
+            BinaryOpType::Assign => write!(f, "="),
+            BinaryOpType::AddAssign => write!(f, "+="),
+            BinaryOpType::SubAssign => write!(f, "-="),
+            BinaryOpType::MulAssign => write!(f, "*="),
+            BinaryOpType::DivAssign => write!(f, "/="),
+            BinaryOpType::ModAssign => write!(f, "%="),
+            BinaryOpType::BitAndAssign => write!(f, "&="),
+            BinaryOpType::BitOrAssign => write!(f, "|="),
+            BinaryOpType::BitXorAssign => write!(f, "^="),
+            BinaryOpType::LShiftAssign => write!(f, "<<="),
+            BinaryOpType::RShiftAssign => write!(f, ">>="),
         }
     }
 }
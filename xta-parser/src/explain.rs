@@ -0,0 +1,155 @@
+// Long-form explanations of the parser's error codes, printed by `xtac explain <code>`
+pub fn explain(code: &str) -> Option<&'static str> {
+    let text = match code {
+        "XP0001" => r#"A token other than the one the grammar requires was found.
+
+Erroneous code example:
+
+    fn main() {
+        let a = 5
+    }
+
+Every statement that is not a block has to end with a semicolon:
+
+    fn main() {
+        let a = 5;
+    }
+"#,
+        "XP0002" => r#"The source contains a character that does not start any token.
+
+Erroneous code example:
+
+    fn main() {
+        let a = 5 $ 2;
+    }
+
+Only the operators of the language can be used between operands:
+
+    fn main() {
+        let a = 5 + 2;
+    }
+"#,
+        "XP0003" => r#"A block comment was opened but never closed.
+
+Erroneous code example:
+
+    /* outer /* inner */
+    fn main() {
+    }
+
+Block comments nest, so every `/*` needs its own `*/`:
+
+    /* outer /* inner */ */
+    fn main() {
+    }
+"#,
+        "XP0004" => r#"A string literal was opened but the file ended before its closing quote.
+
+Erroneous code example:
+
+    fn main() {
+        print("hello);
+    }
+
+Close the string with a `"`:
+
+    fn main() {
+        print("hello");
+    }
+"#,
+        "XP0005" => r#"A string literal contains an escape sequence that does not exist.
+
+Erroneous code example:
+
+    fn main() {
+        print("C:\path");
+    }
+
+The supported escapes are `\n`, `\t`, `\\`, `\"` and `\u{...}`, a literal
+backslash has to be escaped itself:
+
+    fn main() {
+        print("C:\\path");
+    }
+"#,
+        "XP0006" => r#"The left side of an assignment is not a variable.
+
+Erroneous code example:
+
+    fn main() {
+        let a = 1;
+        2 = a;
+    }
+
+Only variables can be assigned to:
+
+    fn main() {
+        let a = 1;
+        a = 2;
+    }
+"#,
+        "XP0007" => r#"An expression was required but the next token cannot start one.
+
+Erroneous code example:
+
+    fn main() {
+        let a = ;
+    }
+
+Give the variable a value:
+
+    fn main() {
+        let a = 0;
+    }
+"#,
+        "XP0008" => r#"A numeric literal does not fit into its type.
+
+Erroneous code example:
+
+    fn main() {
+        print(9223372036854775808);
+    }
+
+`Int` is a 64-bit signed integer, its largest value is 9223372036854775807.
+`Double` literals must stay below about 1.8e308:
+
+    fn main() {
+        print(9223372036854775807);
+    }
+"#,
+        "XP0009" => r#"A numeric literal is malformed.
+
+Erroneous code example:
+
+    fn main() {
+        print(0b102);
+    }
+
+Binary literals only use `0` and `1`, hex literals `0`-`9` and `a`-`f`, and
+octal literals `0`-`7`. Exponents need at least one digit (e.g. `1e-3`):
+
+    fn main() {
+        print(0b101);
+    }
+"#,
+        "XP0010" => r#"Statements and expressions are nested more than 256 levels deep.
+
+Erroneous code example:
+
+    fn main() {
+        print(((((((((((((((((((((((((( ... 1 ... ))))))))))))))))))))))))));
+    }
+
+Deeply nested code has to be split up, e.g. by moving parts of it into
+variables or functions:
+
+    fn main() {
+        let inner = 1;
+        print(inner);
+    }
+"#,
+        _ => return None,
+    };
+
+    Some(text)
+}
@@ -1,2 +1,3 @@
 pub mod ast;
+pub mod explain;
 pub mod parser;
\ No newline at end of file
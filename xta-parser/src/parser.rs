@@ -1,91 +1,123 @@
-use xta_lexer::{scanner::Scanner, token::{Loc, Token, TokenKind}};
+use std::borrow::Cow;
 
-use crate::ast::{CallExpr, ReturnStmt};
+use xta_diagnostics::diagnostic::Diagnostic;
+use xta_lexer::{scanner::Scanner, token::{Loc, Span, Token, TokenKind}};
 
-use super::ast::{BinaryExpr, BinaryOpType, Block, ElifStmt, Expr, FunctionDeclStmt, IdentifierExpr, IfStmt, Literal, LiteralExpr, Param, Stmt, UnaryExpr, UnaryOpType, VarDeclStmt};
+use crate::ast::{BreakStmt, CallExpr, ContinueStmt, ForStmt, LoopStmt, ReturnStmt, WhileStmt};
+
+use super::ast::{Assoc, BinaryExpr, ErrorExpr, BinaryOpType, Block, ElifStmt, Expr, FunctionDeclStmt, IdentifierExpr, IfStmt, Literal, LiteralExpr, Param, Stmt, UnaryExpr, UnaryOpType, VarDeclStmt};
+
+// how deep statements and expressions may nest, the parser and every pass after it walk
+// the tree recursively, so deeper input would overflow the stack
+const MAX_NESTING: usize = 256;
 
 pub struct Parser<'a> {
     scanner: Scanner<'a>,
     token: Token<'a>,
+    // where the last consumed token ends, closes the span of the node being parsed
+    prev_end: usize,
+    // set once the current statement reported an error, silences the errors that follow from it
+    panicking: bool,
+    // every token kind checked for since the last consumed token, reported when none of them matches
+    expected: Vec<TokenKind>,
+    // how many statements and expressions enclose the one being parsed
+    depth: usize,
     pub errors: Vec<Error<'a>>,
 }
 
 impl<'a> Parser<'a> {
-    pub fn new(mut scanner: Scanner<'a>) -> Self {
-        let token = scanner.next_token();
-        
-        Self {
+    pub fn new(scanner: Scanner<'a>) -> Self {
+        let mut parser = Self {
             scanner,
-            token,
+            token: Token::from_kind(TokenKind::EOF),
+            prev_end: 0,
+            panicking: false,
+            expected: Vec::new(),
+            depth: 0,
             errors: Vec::new(),
-        }
+        };
+
+        parser.token = parser.next_token();
+        parser
     }
 
     pub fn parse_file(&mut self) -> Vec<Stmt<'a>> {
         let mut stmts = Vec::new();
 
         while self.peek().kind != TokenKind::EOF {
-            if let Some(stmt) = self.parse_statement() {
-                stmts.push(stmt);
-            } else {
-                self.consume();
-            }
+            stmts.push(self.parse_or_recover());
         }
 
         stmts
     }
 
     pub fn parse_statement(&mut self) -> Option<Stmt<'a>> {
-        let result = match self.peek().kind {
-            TokenKind::Let => self.parse_variable_declaration(),
-            TokenKind::Fn => self.parse_function(),
-            TokenKind::If => self.parse_if(),
-            TokenKind::Return => self.parse_return(),
-            _ => Some(Stmt::Expr(self.parse_expression(None)?)),
-        };
+        let result = self.nested(|parser| match parser.peek().kind {
+            TokenKind::Let | TokenKind::Const => parser.parse_variable_declaration(),
+            TokenKind::Fn => parser.parse_function(),
+            TokenKind::If => parser.parse_if(),
+            TokenKind::Return => parser.parse_return(),
+            TokenKind::While => parser.parse_while(),
+            TokenKind::For => parser.parse_for(),
+            TokenKind::Loop => parser.parse_loop(),
+            TokenKind::Break => parser.parse_break(),
+            TokenKind::Continue => parser.parse_continue(),
+            _ => Some(Stmt::Expr(parser.parse_expression()?)),
+        });
+
+        // a broken statement leaves its `;` to `synchronize`, which would otherwise skip the next statement
+        let stmt = result?;
 
         // NOTE: add here every other statement that doesnt have a semicolon at the end.
-        if matches!(result, Some(Stmt::FunctionDecl(_)) | Some(Stmt::If(_))) {
-            return result;
+        if matches!(stmt, Stmt::FunctionDecl(_) | Stmt::If(_) | Stmt::While(_) | Stmt::For(_) | Stmt::Loop(LoopStmt { unless: None, .. })) {
+            return Some(stmt);
         }
 
         self.expect(TokenKind::Semicolon)?;
-        result
+        Some(stmt)
     }
 
     // Following the next syntax:
     // return <expression>;
     pub fn parse_return(&mut self) -> Option<Stmt<'a>> {
-        self.expect(TokenKind::Return)?;
+        let keyword = self.expect(TokenKind::Return)?;
         // expect any kind of void returns
-        if self.peek().kind == TokenKind::Semicolon {
-            return Some(Stmt::Return(ReturnStmt { value: None }));
+        if self.check(TokenKind::Semicolon) {
+            return Some(Stmt::Return(ReturnStmt { value: None, loc: keyword.loc, span: self.span_from(keyword.span) }));
         }
 
         // expect a value to be returned
-        let value = self.parse_expression(None)?;
-        Some(Stmt::Return(ReturnStmt { value: Some(value) }))
+        let value = self.parse_expression()?;
+        Some(Stmt::Return(ReturnStmt { value: Some(value), loc: keyword.loc, span: self.span_from(keyword.span) }))
     }
 
     // Following the next syntax:
-    // let <var name> = <expression>;
+    // let <var name> (optional) <type> = <expression>;
+    // const <var name> (optional) <type> = <expression>;
     pub fn parse_variable_declaration(&mut self) -> Option<Stmt<'a>> {
-        self.expect(TokenKind::Let)?;
+        let is_const = self.peek().kind == TokenKind::Const;
+        let keyword = self.expect(if is_const { TokenKind::Const } else { TokenKind::Let })?;
         let name = self.expect(TokenKind::Identifier)?;
 
+        let var_type = if self.check(TokenKind::Identifier) {
+            Some(self.consume().text)
+        } else {
+            None
+        };
+
         self.expect(TokenKind::Assign)?;
 
-        let value = self.parse_expression(None)?;
+        let value = self.parse_expression()?;
 
-        Some(Stmt::VarDecl(VarDeclStmt {value: Some(value), name: name.text, is_const: false}))
+        Some(Stmt::VarDecl(VarDeclStmt {value: Some(value), name: name.text, var_type, is_const, loc: keyword.loc, span: self.span_from(keyword.span) }))
     }
 
     // Following the next syntax:
     // if(a > b) { <body> } (optional) elif (...) { <body> } (optional) else { ... }
     pub fn parse_if(&mut self) -> Option<Stmt<'a>> {
-        self.expect(TokenKind::If)?;
-        
-        let condition = self.parse_expression(None)?; 
+        let keyword = self.expect(TokenKind::If)?;
+
+        let condition = self.parse_expression()?; 
 
         let mut elif_branch = Vec::new();
 
@@ -93,100 +125,212 @@ impl<'a> Parser<'a> {
 
         let then = self.parse_scope()?;
 
-        while self.peek().kind == TokenKind::Elif {
+        while self.check(TokenKind::Elif) {
             elif_branch.push(self.parse_elif()?); 
         }
 
         // TODO: fix when no else is added.
-        if self.peek().kind == TokenKind::Else {
+        if self.check(TokenKind::Else) {
             self.consume();
             else_branch = Some(self.parse_scope()?);
         }
 
-        Some(Stmt::If(IfStmt { condition, then, elif_branch, else_branch}))
+        Some(Stmt::If(IfStmt { condition, then, elif_branch, else_branch, loc: keyword.loc, span: self.span_from(keyword.span) }))
     }
 
     pub fn parse_elif(&mut self) -> Option<ElifStmt<'a>> {
-        self.expect(TokenKind::Elif)?;
+        let keyword = self.expect(TokenKind::Elif)?;
         
-        let condition = self.parse_expression(None)?;
+        let condition = self.parse_expression()?;
 
         let then = self.parse_scope()?;
 
-        Some(ElifStmt { condition, then })
+        Some(ElifStmt { condition, then, span: self.span_from(keyword.span) })
+    }
+
+    // Following the next syntax:
+    // while a > b { <body> }
+    pub fn parse_while(&mut self) -> Option<Stmt<'a>> {
+        let keyword = self.expect(TokenKind::While)?;
+
+        let condition = self.parse_expression()?;
+
+        let body = self.parse_scope()?;
+
+        Some(Stmt::While(WhileStmt { condition, body, loc: keyword.loc, span: self.span_from(keyword.span) }))
+    }
+
+    // Following the next syntax:
+    // for i in 0..n { <body> } or for i in 0..=n { <body> }
+    pub fn parse_for(&mut self) -> Option<Stmt<'a>> {
+        let keyword = self.expect(TokenKind::For)?;
+        let var = self.expect(TokenKind::Identifier)?;
+
+        self.expect(TokenKind::In)?;
+
+        let start = self.parse_expression()?;
+
+        let inclusive = self.check(TokenKind::RangeInclusive);
+        self.expect(if inclusive { TokenKind::RangeInclusive } else { TokenKind::Range })?;
+
+        let end = self.parse_expression()?;
+
+        let body = self.parse_scope()?;
+
+        Some(Stmt::For(ForStmt { var: IdentifierExpr { name: var.text, loc: var.loc, span: var.span }, start, end, inclusive, body, loc: keyword.loc, span: self.span_from(keyword.span) }))
+    }
+
+    // Following the next syntax:
+    // loop { <body> } (optional) unless a > b;
+    pub fn parse_loop(&mut self) -> Option<Stmt<'a>> {
+        let keyword = self.expect(TokenKind::Loop)?;
+
+        let body = self.parse_scope()?;
+
+        let unless = if self.check(TokenKind::Unless) {
+            self.consume();
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+
+        Some(Stmt::Loop(LoopStmt { body, unless, loc: keyword.loc, span: self.span_from(keyword.span) }))
+    }
+
+    // Following the next syntax:
+    // break;
+    pub fn parse_break(&mut self) -> Option<Stmt<'a>> {
+        let keyword = self.expect(TokenKind::Break)?;
+        Some(Stmt::Break(BreakStmt { loc: keyword.loc, span: keyword.span }))
+    }
+
+    // Following the next syntax:
+    // continue;
+    pub fn parse_continue(&mut self) -> Option<Stmt<'a>> {
+        let keyword = self.expect(TokenKind::Continue)?;
+        Some(Stmt::Continue(ContinueStmt { loc: keyword.loc, span: keyword.span }))
     }
 
     // Following the next syntax:
     // fn foo(a int, b int) -> int { <body> }
     pub fn parse_function(&mut self) -> Option<Stmt<'a>> {
-        self.expect(TokenKind::Fn)?;
+        let keyword = self.expect(TokenKind::Fn)?;
         let name = self.expect(TokenKind::Identifier)?;
 
-        let params = self.parse_function_params().unwrap_or_default();
+        let params = self.parse_function_params().unwrap_or_else(|| {
+            // skips the rest of a broken parameter list so the body is still parsed
+            while !matches!(self.peek().kind, TokenKind::RightParen | TokenKind::LeftBrace | TokenKind::EOF) {
+                self.consume();
+            }
+            if self.peek().kind == TokenKind::RightParen {
+                self.consume();
+            }
+            Vec::new()
+        });
         
-        let return_type = if self.peek().kind == TokenKind::ReturnTypeArrow {
+        let return_type = if self.check(TokenKind::ReturnTypeArrow) {
             self.consume();
-            Some(self.expect(TokenKind::Identifier)?.text)
+            // a missing type is reported, the body is still parsed
+            self.expect(TokenKind::Identifier).map(|token| token.text)
         } else {
             None
         };
 
         let body =  self.parse_scope()?;
 
-        Some(Stmt::FunctionDecl(FunctionDeclStmt {name: name.text, params, body, return_type}))
+        Some(Stmt::FunctionDecl(FunctionDeclStmt {name: name.text, name_span: name.span, params, body, return_type, loc: keyword.loc, span: self.span_from(keyword.span) }))
     }
 
-    pub fn parse_expression(&mut self, prec: Option<i8>) -> Option<Expr<'a>> {
-        let prec = prec.unwrap_or(-1);
+    pub fn parse_expression(&mut self) -> Option<Expr<'a>> {
+        self.nested(|parser| parser.parse_binary(0))
+    }
 
+    // precedence climbing over the operator table in `ast.rs`, parses an operand followed
+    // by every binary operator that binds at least as tight as `min_prec`
+    fn parse_binary(&mut self, min_prec: u8) -> Option<Expr<'a>> {
         let mut lhs = self.parse_unary()?;
+        let mut chained = false;
 
-        if self.peek().kind == TokenKind::Assign && prec < BinaryOpType::Assign.prec() as i8 {
-            return self.parse_reassign(lhs);
-        }
-
-        while let Some(op) = self.parse_binary_op() {
-            let op_prec = op.prec() as i8;
-            if op_prec <= prec {
+        while let Some(operator) = self.parse_binary_op() {
+            let (prec, assoc) = operator.binding();
+            if prec < min_prec {
                 break;
             }
 
-            self.consume();
-            let rhs = self.parse_expression(Some(op_prec))?;
+            // `a + b + c` nests the earlier operators one level deeper for every operator that follows
+            if chained {
+                self.deeper()?;
+            }
+            chained = true;
+
+            let op_span = self.consume().span;
+            if operator.is_assignment() && !matches!(lhs, Expr::Identifier(_)) {
+                self.report(Error::ExpectedId { loc: lhs.loc(), span: lhs.span(), found: self.peek().clone() });
+                return None;
+            }
+
+            let rhs = self.nested(|parser| parser.parse_binary(operand_prec(prec, assoc)))?;
             lhs = Expr::Binary(BinaryExpr {
+                loc: lhs.loc(),
+                span: lhs.span().to(rhs.span()),
+                op_span,
                 left: Box::new(lhs),
                 right: Box::new(rhs),
-                operator: op,
-                loc: self.peek().loc.clone(),
+                operator,
             })
         }
 
         Some(lhs)
     }
 
+    // a prefix operator applies to every operator above it in the table:
+    // `-a * b` is `(-a) * b`, `-a ** b` is `-(a ** b)` and `-a++` is `-(a++)`
     pub fn parse_unary(&mut self) -> Option<Expr<'a>> {
-        match self.peek().kind {
-            TokenKind::Min => {
-                let loc = self.peek().loc.clone();
-                self.consume();
-                let op = self.parse_expression(Some(UnaryOpType::Neg as i8));
-                Some(Expr::Unary(UnaryExpr {
-                    operand: Box::new(op.unwrap()),
-                    operator: UnaryOpType::Neg,
-                    loc
-                }))
-            }
-            TokenKind::Not => {
-                let loc = self.peek().loc.clone();
-                self.consume();
-                let op = self.parse_expression(Some(UnaryOpType::Not as i8));
-                Some(Expr::Unary(UnaryExpr {
-                    operand: Box::new(op.unwrap()),
-                    operator: UnaryOpType::Not,
-                    loc
-                }))
+        let operator = match self.peek().kind {
+            TokenKind::Min => UnaryOpType::Neg,
+            TokenKind::Not => UnaryOpType::Not,
+            TokenKind::BNot => UnaryOpType::BitNot,
+            TokenKind::Inc => UnaryOpType::Inc,
+            TokenKind::Dec => UnaryOpType::Dec,
+            _ => return self.parse_postfix(),
+        };
+
+        let token = self.consume();
+        let operand = self.nested(|parser| parser.parse_binary(operand_prec(operator.prec(), operator.assoc())))?;
+
+        Some(Expr::Unary(UnaryExpr {
+            operand: Box::new(operand),
+            operator,
+            loc: token.loc,
+            span: self.span_from(token.span),
+        }))
+    }
+
+    // Following the next syntax:
+    // <primary>++ or <primary>--
+    fn parse_postfix(&mut self) -> Option<Expr<'a>> {
+        let mut expr = self.parse_primary()?;
+        let mut chained = false;
+
+        loop {
+            let operator = match self.peek().kind {
+                TokenKind::Inc => UnaryOpType::PostInc,
+                TokenKind::Dec => UnaryOpType::PostDec,
+                _ => return Some(expr),
+            };
+
+            if chained {
+                self.deeper()?;
             }
-            _ => self.parse_primary()
+            chained = true;
+
+            let op_span = self.consume().span;
+            expr = Expr::Unary(UnaryExpr {
+                loc: expr.loc(),
+                span: expr.span().to(op_span),
+                operand: Box::new(expr),
+                operator,
+            });
         }
     }
 }
@@ -194,57 +338,165 @@ impl<'a> Parser<'a> {
 // Private functions
 impl <'a> Parser <'a> {
     fn consume(&mut self) -> Token<'a> {
-        std::mem::replace(&mut self.token, self.scanner.next_token())
+        let next = self.next_token();
+        let token = std::mem::replace(&mut self.token, next);
+        self.prev_end = token.span.end;
+        self.expected.clear();
+        token
+    }
+
+    // parses one level deeper, the levels counted by `parse` itself are dropped once it returns
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let depth = self.depth;
+        self.deeper()?;
+
+        let result = parse(self);
+        self.depth = depth;
+
+        result
+    }
+
+    // counts one more level of nesting, giving up on input nested past `MAX_NESTING`
+    fn deeper(&mut self) -> Option<()> {
+        if self.depth >= MAX_NESTING {
+            let token = self.peek().clone();
+            self.report(Error::NestingTooDeep { loc: token.loc, span: token.span });
+            return None;
+        }
+
+        self.depth += 1;
+        Some(())
+    }
+
+    // a span from `start` up to the end of the last consumed token
+    fn span_from(&self, start: Span) -> Span {
+        Span::new(start.start, self.prev_end.max(start.start))
+    }
+
+    // pulls the next token from the scanner, reporting and skipping illegal ones
+    fn next_token(&mut self) -> Token<'a> {
+        loop {
+            let token = self.scanner.next_token();
+            match token.kind {
+                // the scanner hands out the opening `/*` of a block comment that never ends
+                TokenKind::UnterminatedComment => self.errors.push(Error::UnterminatedComment { loc: token.loc.clone(), span: token.span }),
+                TokenKind::UnterminatedString => self.errors.push(Error::UnterminatedString { loc: token.loc.clone(), span: token.span }),
+                TokenKind::Illegal => self.errors.push(Error::Illegal { loc: token.loc.clone(), found: token }),
+                // invalid numbers are handed out too, they are reported where an expression is parsed
+                _ => return token,
+            }
+        }
+    }
+
+    // records an error unless the current statement already failed. malformed literals are
+    // pushed directly instead, they are replaced by a placeholder and parsing goes on as usual
+    fn report(&mut self, error: Error<'a>) {
+        if !self.panicking {
+            self.errors.push(error);
+        }
+        self.panicking = true;
+    }
+
+    // parses a statement, on failure skips to the next statement boundary and
+    // keeps the skipped code as an error node
+    fn parse_or_recover(&mut self) -> Stmt<'a> {
+        let start = self.peek().clone();
+        self.panicking = false;
+
+        if let Some(stmt) = self.parse_statement() {
+            return stmt;
+        }
+
+        self.synchronize();
+
+        // a token that can neither start nor end a statement, e.g. a stray `}` at the top level
+        if self.prev_end <= start.span.start && self.peek().kind != TokenKind::EOF {
+            self.consume();
+        }
+
+        Stmt::Expr(Expr::Error(ErrorExpr { loc: start.loc, span: self.span_from(start.span) }))
+    }
+
+    // skips tokens up to the end of the broken statement: past a `;`, or up to a `}` or a
+    // keyword starting the next statement. blocks inside the broken statement are skipped as a whole
+    fn synchronize(&mut self) {
+        let mut depth = 0;
+
+        loop {
+            match self.peek().kind {
+                TokenKind::EOF => return,
+                TokenKind::Semicolon if depth == 0 => {
+                    self.consume();
+                    return;
+                }
+                TokenKind::RightBrace if depth == 0 => return,
+                TokenKind::Fn | TokenKind::Let | TokenKind::Const | TokenKind::If | TokenKind::While | TokenKind::For | TokenKind::Loop | TokenKind::Return if depth == 0 => return,
+                TokenKind::LeftBrace => depth += 1,
+                TokenKind::RightBrace => depth -= 1,
+                _ => {}
+            }
+
+            self.consume();
+        }
     }
 
     fn peek(&self) -> &Token<'a> {
         &self.token
     }
 
+    // whether the current token is of the given kind, remembering the kind for error messages
+    fn check(&mut self, kind: TokenKind) -> bool {
+        let matches = self.token.kind == kind;
+        if !self.expected.contains(&kind) {
+            self.expected.push(kind);
+        }
+        matches
+    }
+
     fn expect(&mut self, kind: TokenKind) -> Option<Token<'a>> {
-        if self.token.kind == kind {
+        if self.check(kind) {
             Some(self.consume())
         } else {
-            self.errors.push(Error::Expected {
-                loc: self.token.loc.clone(),
-                expected: Token::from_kind(kind),
-                found: self.token.clone(),
-            });
-
+            self.report_expected();
             None
         }
     }
 
+    // reports that none of the token kinds checked for so far was found
+    fn report_expected(&mut self) {
+        self.report(Error::Expected {
+            loc: self.token.loc.clone(),
+            expected: self.expected.clone(),
+            found: self.token.clone(),
+        });
+    }
+
     fn parse_function_params(&mut self) -> Option<Vec<Param<'a>>> {
 
         self.expect(TokenKind::LeftParen)?;
         
         // handle params
         let mut params = Vec::new();
-        let has_params = self.peek().kind != TokenKind::RightParen;
+        let has_params = !self.check(TokenKind::RightParen);
 
         if has_params {
             loop {
                 let param_name = self.expect(TokenKind::Identifier)?;
                 let param_type = self.expect(TokenKind::Identifier)?;
                 
-                params.push(Param { name: param_name.text, param_type: param_type.text});
+                params.push(Param { name: param_name.text, param_type: param_type.text, loc: param_name.loc, span: param_name.span.to(param_type.span) });
 
-                if self.peek().kind == TokenKind::RightParen {
-                    break;
-                }
-
-                if self.peek().kind == TokenKind::Comma {
+                if self.check(TokenKind::Comma) {
                     self.consume();
                     continue;
-                } else {
-                    self.errors.push(Error::Expected {
-                        loc: self.peek().loc.clone(),
-                        expected: Token::from_kind(TokenKind::Comma),
-                        found: self.peek().clone(),
-                    });
                 }
 
+                if self.check(TokenKind::RightParen) {
+                    break;
+                }
+
+                self.report_expected();
+
             };
         }
 
@@ -256,15 +508,12 @@ impl <'a> Parser <'a> {
         self.expect(TokenKind::LeftBrace)?;
 
         let mut stmts = Vec::new();
-        while self.peek().kind != TokenKind::RightBrace {
-            if let Some(stmt) = self.parse_statement() {
-                stmts.push(stmt);
-            } else {
-                self.consume();
-            }
+        while !matches!(self.peek().kind, TokenKind::RightBrace | TokenKind::EOF) {
+            stmts.push(self.parse_or_recover());
         }
 
-        self.expect(TokenKind::RightBrace)?;
+        // a block cut off by the end of the file is kept, only the missing `}` is reported
+        self.expect(TokenKind::RightBrace);
 
         Some(stmts)
     }
@@ -272,15 +521,34 @@ impl <'a> Parser <'a> {
     fn parse_primary(&mut self) -> Option<Expr<'a>> {
         match self.peek().kind {
             TokenKind::Integer => self.parse_integer(),
+            TokenKind::Double => self.parse_double(),
+            TokenKind::InvalidNumber => {
+                let token = self.consume();
+                self.errors.push(Error::InvalidNumber { loc: token.loc.clone(), found: token.clone() });
+                Some(Expr::Error(ErrorExpr { loc: token.loc, span: token.span }))
+            }
+            TokenKind::Boolean => {
+                let token = self.consume();
+                Some(Expr::Literal(LiteralExpr { value: Literal::Boolean(token.text == "true"), loc: token.loc, span: token.span }))
+            }
+            TokenKind::None => {
+                let token = self.consume();
+                Some(Expr::Literal(LiteralExpr { value: Literal::None, loc: token.loc, span: token.span }))
+            }
             TokenKind::Identifier => self.parse_identifier(),
             TokenKind::String => self.parse_string(),
             TokenKind::LeftParen => {
                 self.consume();
-                let expr = self.parse_expression(None);
+                let expr = self.parse_expression();
                 self.expect(TokenKind::RightParen)?;
                 expr
             }
-            _ => None,
+            _ => {
+                // keeps the surrounding expression intact, the token is left for the caller
+                let token = self.peek().clone();
+                self.report(Error::ExpectedExpression { loc: token.loc.clone(), expected: EXPRESSION_START.to_vec(), found: token.clone() });
+                Some(Expr::Error(ErrorExpr { loc: token.loc, span: Span::new(token.span.start, token.span.start) }))
+            }
         }
     }
 
@@ -290,7 +558,19 @@ impl <'a> Parser <'a> {
             TokenKind::Min => Some(BinaryOpType::Sub),
             TokenKind::Mul => Some(BinaryOpType::Mul),
             TokenKind::Div => Some(BinaryOpType::Div),
+            TokenKind::Mod => Some(BinaryOpType::Mod),
+            TokenKind::Pow => Some(BinaryOpType::Pow),
             TokenKind::Assign => Some(BinaryOpType::Assign),
+            TokenKind::PlusAssign => Some(BinaryOpType::AddAssign),
+            TokenKind::MinAssign => Some(BinaryOpType::SubAssign),
+            TokenKind::MulAssign => Some(BinaryOpType::MulAssign),
+            TokenKind::DivAssign => Some(BinaryOpType::DivAssign),
+            TokenKind::ModAssign => Some(BinaryOpType::ModAssign),
+            TokenKind::BAndAssign => Some(BinaryOpType::BitAndAssign),
+            TokenKind::BOrAssign => Some(BinaryOpType::BitOrAssign),
+            TokenKind::XorAssign => Some(BinaryOpType::BitXorAssign),
+            TokenKind::RightShAssign => Some(BinaryOpType::RShiftAssign),
+            TokenKind::LeftShAssign => Some(BinaryOpType::LShiftAssign),
             TokenKind::And => Some(BinaryOpType::And),
             TokenKind::Or => Some(BinaryOpType::Or),
             TokenKind::Equals => Some(BinaryOpType::Eq),
@@ -308,42 +588,67 @@ impl <'a> Parser <'a> {
         }
     }
 
-    fn parse_reassign(&mut self, lhs: Expr<'a>) -> Option<Expr<'a>> {
-        self.expect(TokenKind::Assign)?;
-        if ! matches!(lhs, Expr::Identifier(_)) {
-            self.errors.push(Error::ExpectedId { loc: lhs.loc(), found: self.peek().clone() });
-            None
-        } else {
-            let rhs = self.parse_expression(Some(BinaryOpType::Assign.prec() as i8))?;
-            let result = Some(Expr::Binary(BinaryExpr {
-                left: Box::new(lhs),
-                right: Box::new(rhs),
-                operator: BinaryOpType::Assign,
-                loc: self.peek().loc.clone(),
-            }));
-
-            result
-        }
-    }
-
     // primary expression parsing
     fn parse_integer(&mut self) -> Option<Expr<'a>> {
         let token = self.expect(TokenKind::Integer)?;
-        Some(Expr::Literal(LiteralExpr { value: Literal::Integer(token.text.parse().unwrap()), loc: token.loc.clone() }))
+
+        let digits = token.text.replace('_', "");
+        let value = match digits.get(..2) {
+            Some("0x") => i64::from_str_radix(&digits[2..], 16),
+            Some("0b") => i64::from_str_radix(&digits[2..], 2),
+            Some("0o") => i64::from_str_radix(&digits[2..], 8),
+            _ => digits.parse(),
+        };
+
+        let Ok(value) = value else {
+            self.errors.push(Error::OutOfRange { loc: token.loc.clone(), found: token.clone() });
+            return Some(Expr::Error(ErrorExpr { loc: token.loc, span: token.span }));
+        };
+
+        Some(Expr::Literal(LiteralExpr { value: Literal::Integer(value), loc: token.loc.clone(), span: token.span }))
+    }
+
+    fn parse_double(&mut self) -> Option<Expr<'a>> {
+        let token = self.expect(TokenKind::Double)?;
+        // too large values parse as infinity rather than failing
+        let value = match token.text.replace('_', "").parse::<f64>() {
+            Ok(value) if value.is_finite() => value,
+            _ => {
+                self.errors.push(Error::OutOfRange { loc: token.loc.clone(), found: token.clone() });
+                return Some(Expr::Error(ErrorExpr { loc: token.loc, span: token.span }));
+            }
+        };
+
+        Some(Expr::Literal(LiteralExpr { value: Literal::Double(value), loc: token.loc.clone(), span: token.span }))
     }
 
     fn parse_string(&mut self) -> Option<Expr<'a>> {
         let token = self.expect(TokenKind::String)?;
-        Some(Expr::Literal(LiteralExpr { value: Literal::String(token.text), loc: token.loc.clone() }))
+
+        // the token still holds the surrounding quotes
+        let raw = &token.text[1..token.text.len() - 1];
+        let value = match unescape(raw) {
+            Ok(value) => value,
+            Err((offset, escape)) => {
+                // `offset` is relative to `raw`, skip the opening quote as well
+                let loc = loc_at(&token.loc, token.text, offset + 1);
+                let start = token.span.start + offset + 1;
+                let span = Span::new(start, start + escape.len());
+                self.errors.push(Error::InvalidEscape { loc, span, escape: escape.to_string() });
+                Cow::Borrowed(raw)
+            }
+        };
+
+        Some(Expr::Literal(LiteralExpr { value: Literal::String(value), loc: token.loc.clone(), span: token.span }))
     }
 
     fn parse_identifier(&mut self) -> Option<Expr<'a>> {
         let token = self.expect(TokenKind::Identifier)?;
 
-        if self.peek().kind == TokenKind::LeftParen {
+        if self.check(TokenKind::LeftParen) {
             self.parse_fn_call(token)
         } else {
-            Some(Expr::Identifier(IdentifierExpr { name: token.text, loc: token.loc.clone() }))
+            Some(Expr::Identifier(IdentifierExpr { name: token.text, loc: token.loc.clone(), span: token.span }))
         }
 
     }
@@ -352,36 +657,234 @@ impl <'a> Parser <'a> {
     fn parse_fn_call(&mut self, identifier: Token<'a>) -> Option<Expr<'a>> {
         self.expect(TokenKind::LeftParen)?;
         let mut args = Vec::new();
-        while self.peek().kind != TokenKind::RightParen {
-            let arg = self.parse_expression(None)?;
+        while !self.check(TokenKind::RightParen) {
+            let arg = self.parse_expression()?;
             args.push(arg);
 
-            if self.peek().kind == TokenKind::RightParen {
+            if self.check(TokenKind::Comma) {
+                self.consume();
+                continue;
+            }
+
+            if self.check(TokenKind::RightParen) {
                 break;
             }
 
-            if self.peek().kind == TokenKind::Comma {
-                self.consume();
-                continue;
-            } else {
-                self.errors.push(Error::Expected {
-                    loc: self.peek().loc.clone(),
-                    expected: Token::from_kind(TokenKind::Comma),
-                    found: self.peek().clone(),
-                });
+            self.report_expected();
+
+            // a missing comma between two arguments is skipped over, anything else ends the call
+            if !EXPRESSION_START.contains(&self.peek().kind) {
+                return None;
             }
         }
 
         self.expect(TokenKind::RightParen)?;
-        Some(Expr::Call(CallExpr { name: identifier.text, args, loc: identifier.loc.clone() }))
+        Some(Expr::Call(CallExpr { name: identifier.text, args, loc: identifier.loc.clone(), span: self.span_from(identifier.span) }))
+    }
+}
+
+// the tokens an expression can begin with
+const EXPRESSION_START: &[TokenKind] = &[
+    TokenKind::Integer, TokenKind::Double, TokenKind::String, TokenKind::Boolean, TokenKind::None,
+    TokenKind::Identifier, TokenKind::LeftParen, TokenKind::Min, TokenKind::Not, TokenKind::BNot, TokenKind::Inc, TokenKind::Dec,
+];
+
+// the lowest precedence an operator's right operand may bind at, a right-associative
+// operator accepts itself again (`a = b = c`) while a left-associative one does not
+fn operand_prec(prec: u8, assoc: Assoc) -> u8 {
+    match assoc {
+        Assoc::Left => prec + 1,
+        Assoc::Right => prec,
+    }
+}
+
+// "Expected `;`, found `}`" or "Expected one of `,`, `)`; found `{`"
+fn expected_message(expected: &[TokenKind], found: &Token) -> String {
+    let found = match found.kind {
+        TokenKind::EOF => found.kind.to_string(),
+        _ => format!("`{found}`"),
+    };
+
+    match expected {
+        [kind] => format!("Expected {}, found {}", describe(kind), found),
+        _ => {
+            let kinds: Vec<String> = expected.iter().map(describe).collect();
+            format!("Expected one of {}; found {}", kinds.join(", "), found)
+        }
+    }
+}
+
+// quotes tokens that are always spelled the same, e.g. `;`, but not identifiers or literals
+fn describe(kind: &TokenKind) -> String {
+    match kind {
+        TokenKind::Identifier | TokenKind::Integer | TokenKind::Double | TokenKind::String | TokenKind::Boolean | TokenKind::EOF | TokenKind::Illegal => kind.to_string(),
+        _ => format!("`{kind}`"),
     }
 }
 
+// decodes the escape sequences of a string literal, borrowing when there are none.
+// on failure returns the byte offset and text of the invalid escape
+fn unescape(raw: &str) -> Result<Cow<'_, str>, (usize, &str)> {
+    if !raw.contains('\\') {
+        return Ok(Cow::Borrowed(raw));
+    }
+
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.char_indices().peekable();
+
+    while let Some((offset, c)) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        let invalid = |end: usize| (offset, &raw[offset..end]);
+        let Some((at, escaped)) = chars.next() else {
+            return Err(invalid(raw.len()));
+        };
+        let end = at + escaped.len_utf8();
+
+        match escaped {
+            'n' => value.push('\n'),
+            't' => value.push('\t'),
+            '\\' => value.push('\\'),
+            '"' => value.push('"'),
+            'u' => {
+                // \u{1F600}, one to six hex digits
+                if chars.next_if(|(_, c)| *c == '{').is_none() {
+                    return Err(invalid(end));
+                }
+
+                let digits_start = end + 1;
+                let mut digits_end = digits_start;
+                while let Some((at, c)) = chars.next_if(|(_, c)| c.is_ascii_hexdigit()) {
+                    digits_end = at + c.len_utf8();
+                }
+
+                let Some((close, _)) = chars.next_if(|(_, c)| *c == '}') else {
+                    return Err(invalid(digits_end));
+                };
+
+                let digits = &raw[digits_start..digits_end];
+                let decoded = (1..=6)
+                    .contains(&digits.len())
+                    .then(|| u32::from_str_radix(digits, 16).ok())
+                    .flatten()
+                    .and_then(char::from_u32);
+
+                match decoded {
+                    Some(c) => value.push(c),
+                    None => return Err(invalid(close + 1)),
+                }
+            }
+            _ => return Err(invalid(end)),
+        }
+    }
+
+    Ok(Cow::Owned(value))
+}
+
+// the location of the byte at `offset` inside a token's text
+fn loc_at(start: &Loc, text: &str, offset: usize) -> Loc {
+    let mut loc = start.clone();
+
+    for c in text[..offset].chars() {
+        if c == '\n' {
+            loc.row += 1;
+            loc.col = 1;
+        } else {
+            loc.col += 1;
+        }
+    }
+
+    loc
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum Error<'a> {
-    #[error("~ ({loc}) : Expected `{expected}`, found `{found}`")]
-    Expected { loc: Loc, expected: Token<'a>, found: Token<'a> },
+    #[error("~ ({loc}) : {}", expected_message(expected, found))]
+    Expected { loc: Loc, expected: Vec<TokenKind>, found: Token<'a> },
+
+    #[error("~ ({loc}) : Illegal token `{found}`")]
+    Illegal { loc: Loc, found: Token<'a> },
+
+    #[error("~ ({loc}) : Unterminated block comment")]
+    UnterminatedComment { loc: Loc, span: Span },
+
+    #[error("~ ({loc}) : Unterminated string literal")]
+    UnterminatedString { loc: Loc, span: Span },
+
+    #[error("~ ({loc}) : Invalid escape sequence `{escape}`")]
+    InvalidEscape { loc: Loc, span: Span, escape: String },
 
     #[error("~ ({loc}) : Expected an identifier, found `{found}`")]
-    ExpectedId {loc: Loc, found: Token<'a> }
+    ExpectedId {loc: Loc, span: Span, found: Token<'a> },
+
+    #[error("~ ({loc}) : {}", expected_message(expected, found))]
+    ExpectedExpression { loc: Loc, expected: Vec<TokenKind>, found: Token<'a> },
+
+    #[error("~ ({loc}) : Numeric literal `{found}` is out of range")]
+    OutOfRange { loc: Loc, found: Token<'a> },
+
+    #[error("~ ({loc}) : Invalid numeric literal `{found}`")]
+    InvalidNumber { loc: Loc, found: Token<'a> },
+
+    #[error("~ ({loc}) : Nesting is too deep")]
+    NestingTooDeep { loc: Loc, span: Span },
+}
+
+impl Error<'_> {
+    // a stable identifier for the kind of error, explained by `xtac explain <code>`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Expected { .. } => "XP0001",
+            Error::Illegal { .. } => "XP0002",
+            Error::UnterminatedComment { .. } => "XP0003",
+            Error::UnterminatedString { .. } => "XP0004",
+            Error::InvalidEscape { .. } => "XP0005",
+            Error::ExpectedId { .. } => "XP0006",
+            Error::ExpectedExpression { .. } => "XP0007",
+            Error::OutOfRange { .. } => "XP0008",
+            Error::InvalidNumber { .. } => "XP0009",
+            Error::NestingTooDeep { .. } => "XP0010",
+        }
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let diagnostic = match self {
+            Error::Expected { expected, found, .. } => Diagnostic::error(expected_message(expected, found))
+                .with_label(found.span, match expected.as_slice() {
+                    [kind] => format!("expected {}", describe(kind)),
+                    _ => "unexpected token".to_string(),
+                }),
+            Error::Illegal { found, .. } => Diagnostic::error(format!("Illegal token `{found}`"))
+                .with_label(found.span, "not a valid token"),
+            Error::UnterminatedComment { span, .. } => Diagnostic::error("Unterminated block comment")
+                .with_label(*span, "this comment is never closed")
+                .with_note("block comments nest, every `/*` needs its own `*/`"),
+            Error::UnterminatedString { span, .. } => Diagnostic::error("Unterminated string literal")
+                .with_label(*span, "missing a closing `\"`"),
+            Error::InvalidEscape { span, escape, .. } => Diagnostic::error(format!("Invalid escape sequence `{escape}`"))
+                .with_label(*span, "unknown escape")
+                .with_note("supported escapes are `\\n`, `\\t`, `\\\\`, `\\\"` and `\\u{...}`"),
+            Error::ExpectedId { span, found, .. } => Diagnostic::error(format!("Expected an identifier, found `{found}`"))
+                .with_label(*span, "only variables can be assigned to"),
+            Error::ExpectedExpression { expected, found, .. } => Diagnostic::error(expected_message(expected, found))
+                .with_label(found.span, "expected an expression"),
+            Error::OutOfRange { found, .. } if found.kind == TokenKind::Double => Diagnostic::error(format!("Numeric literal `{found}` is out of range"))
+                .with_label(found.span, "does not fit into a `Double`")
+                .with_note(format!("the largest `Double` is about {:e}", f64::MAX)),
+            Error::OutOfRange { found, .. } => Diagnostic::error(format!("Numeric literal `{found}` is out of range"))
+                .with_label(found.span, "does not fit into an `Int`")
+                .with_note(format!("`Int` values range from {} to {}", i64::MIN, i64::MAX)),
+            Error::InvalidNumber { found, .. } => Diagnostic::error(format!("Invalid numeric literal `{found}`"))
+                .with_label(found.span, "malformed number")
+                .with_note("numbers are written as `42`, `1_000`, `0xFF`, `0b1010`, `0o17`, `1.5` or `1.5e-3`"),
+            Error::NestingTooDeep { span, .. } => Diagnostic::error("Nesting is too deep")
+                .with_label(*span, format!("more than {MAX_NESTING} nested statements and expressions"))
+                .with_note("move parts of the code into variables or functions"),
+        };
+
+        diagnostic.with_code(self.code())
+    }
 }
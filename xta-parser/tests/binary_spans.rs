@@ -0,0 +1,87 @@
+use xta_lexer::{scanner::Scanner, token::{Loc, Span}};
+use xta_parser::{ast::{BinaryExpr, BinaryOpType, Expr, Stmt}, parser::Parser};
+
+fn parse_expr(source: &str) -> Expr<'_> {
+    let mut parser = Parser::new(Scanner::new(source));
+    let stmt = parser.parse_statement();
+    assert!(parser.errors.is_empty(), "unexpected errors: {:?}", parser.errors);
+
+    match stmt {
+        Some(Stmt::Expr(expr)) => expr,
+        other => panic!("expected an expression statement, found {:?}", other),
+    }
+}
+
+fn binary<'e, 'a>(expr: &'e Expr<'a>) -> &'e BinaryExpr<'a> {
+    match expr {
+        Expr::Binary(binary) => binary,
+        other => panic!("expected a binary expression, found {:?}", other),
+    }
+}
+
+#[test]
+fn binary_span_covers_both_operands() {
+    let source = "a + b * c;";
+    let expr = parse_expr(source);
+    let add = binary(&expr);
+
+    assert_eq!(add.operator, BinaryOpType::Add);
+    assert_eq!(add.span, Span::new(0, 9));
+    assert_eq!(add.op_span, Span::new(2, 3));
+    assert_eq!(&source[add.span.start..add.span.end], "a + b * c");
+
+    let mul = binary(&add.right);
+    assert_eq!(mul.span, Span::new(4, 9));
+    assert_eq!(mul.op_span, Span::new(6, 7));
+}
+
+#[test]
+fn binary_loc_points_at_left_operand() {
+    let expr = parse_expr("foo(a + b, c);");
+    let Expr::Call(call) = &expr else {
+        panic!("expected a call, found {:?}", expr);
+    };
+
+    let add = binary(&call.args[0]);
+    assert_eq!(add.loc, Loc { row: 0, col: 5 });
+    assert_eq!(add.span, Span::new(4, 9));
+}
+
+#[test]
+fn binary_loc_ignores_following_lines() {
+    let source = "x\n  + y\n\n;";
+    let expr = parse_expr(source);
+    let add = binary(&expr);
+
+    assert_eq!(add.loc, Loc { row: 0, col: 1 });
+    assert_eq!(add.span, Span::new(0, 7));
+    assert_eq!(add.op_span, Span::new(4, 5));
+}
+
+#[test]
+fn assignment_span_covers_target_and_value() {
+    let source = "total = total + 1;";
+    let expr = parse_expr(source);
+    let assign = binary(&expr);
+
+    assert_eq!(assign.operator, BinaryOpType::Assign);
+    assert_eq!(assign.loc, Loc { row: 0, col: 1 });
+    assert_eq!(assign.span, Span::new(0, 17));
+    assert_eq!(assign.op_span, Span::new(6, 7));
+
+    let add = binary(&assign.right);
+    assert_eq!(add.loc, Loc { row: 0, col: 9 });
+    assert_eq!(add.span, Span::new(8, 17));
+}
+
+#[test]
+fn left_associative_chain_grows_from_the_left() {
+    let expr = parse_expr("a - b - c;");
+    let outer = binary(&expr);
+    let inner = binary(&outer.left);
+
+    assert_eq!(outer.span, Span::new(0, 9));
+    assert_eq!(outer.op_span, Span::new(6, 7));
+    assert_eq!(inner.span, Span::new(0, 5));
+    assert_eq!(inner.op_span, Span::new(2, 3));
+}
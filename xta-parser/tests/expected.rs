@@ -0,0 +1,62 @@
+use xta_lexer::{scanner::Scanner, token::{Loc, TokenKind}};
+use xta_parser::parser::{Error, Parser};
+
+fn parse_errors(source: &str) -> Vec<Error<'_>> {
+    let mut parser = Parser::new(Scanner::new(source));
+    parser.parse_file();
+    parser.errors
+}
+
+#[test]
+fn parameter_list_expects_a_comma_or_a_closing_paren() {
+    let errors = parse_errors("fn add(a Int {\n}\nfn main() {}");
+
+    match errors.as_slice() {
+        [error @ Error::Expected { loc, expected, found }] => {
+            assert_eq!(*loc, Loc { row: 0, col: 14 });
+            assert_eq!(*expected, [TokenKind::Comma, TokenKind::RightParen]);
+            assert_eq!(found.kind, TokenKind::LeftBrace);
+            assert_eq!(error.to_diagnostic().message, "Expected one of `,`, `)`; found `{`");
+        }
+        errors => panic!("expected a single `Expected` error, found {:?}", errors),
+    }
+}
+
+#[test]
+fn missing_semicolon_expects_only_a_semicolon() {
+    let errors = parse_errors("fn main() {\n    let a = 5\n}");
+
+    match errors.as_slice() {
+        [error @ Error::Expected { loc, expected, found }] => {
+            assert_eq!(*loc, Loc { row: 2, col: 1 });
+            assert_eq!(*expected, [TokenKind::Semicolon]);
+            assert_eq!(found.kind, TokenKind::RightBrace);
+            assert_eq!(error.to_diagnostic().message, "Expected `;`, found `}`");
+        }
+        errors => panic!("expected a single `Expected` error, found {:?}", errors),
+    }
+
+    // the end of the file is described rather than quoted
+    let errors = parse_errors("fn main() {\n    let a = 5");
+    assert!(
+        errors.iter().any(|error| error.to_diagnostic().message == "Expected `;`, found end of file"),
+        "found {:?}",
+        errors
+    );
+}
+
+#[test]
+fn missing_expression_lists_every_expression_start() {
+    let errors = parse_errors("fn main() {\n    let a = ;\n}");
+
+    match errors.as_slice() {
+        [error @ Error::ExpectedExpression { found, .. }] => {
+            assert_eq!(found.kind, TokenKind::Semicolon);
+            assert_eq!(
+                error.to_diagnostic().message,
+                "Expected one of integer, double, string, boolean, `None`, identifier, `(`, `-`, `!`, `~`, `++`, `--`; found `;`"
+            );
+        }
+        errors => panic!("expected a single `ExpectedExpression` error, found {:?}", errors),
+    }
+}
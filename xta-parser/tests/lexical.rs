@@ -0,0 +1,89 @@
+use xta_lexer::{scanner::Scanner, token::{Loc, Span}};
+use xta_parser::{ast::{Expr, Literal, Stmt}, parser::{Error, Parser}};
+
+fn parse_file(source: &str) -> (Vec<Stmt<'_>>, Vec<Error<'_>>) {
+    let mut parser = Parser::new(Scanner::new(source));
+    let stmts = parser.parse_file();
+    (stmts, parser.errors)
+}
+
+// the decoded value of a string literal statement, along with the errors found on the way
+fn parse_string(source: &str) -> (String, Vec<Error<'_>>) {
+    let mut parser = Parser::new(Scanner::new(source));
+    let value = match parser.parse_statement() {
+        Some(Stmt::Expr(Expr::Literal(literal))) => match literal.value {
+            Literal::String(value) => value.into_owned(),
+            other => panic!("expected a string, found {:?}", other),
+        },
+        other => panic!("expected a literal, found {:?}", other),
+    };
+
+    (value, parser.errors)
+}
+
+// the text, loc and span of the single invalid escape in a string literal statement
+fn invalid_escape(source: &str) -> (String, Loc, Span) {
+    match parse_string(source).1.as_slice() {
+        [Error::InvalidEscape { loc, span, escape }] => (escape.clone(), loc.clone(), *span),
+        errors => panic!("`{source}`: expected a single `InvalidEscape` error, found {:?}", errors),
+    }
+}
+
+#[test]
+fn unterminated_comment_is_reported_at_its_start() {
+    let (stmts, errors) = parse_file("fn main() {}\n  /* open /* nested */");
+
+    assert_eq!(stmts.len(), 1);
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [Error::UnterminatedComment { loc: Loc { row: 1, col: 3 }, span }] if *span == Span::new(15, 17)
+        ),
+        "found {:?}",
+        errors
+    );
+}
+
+#[test]
+fn comments_between_tokens_keep_their_locs() {
+    let (_, errors) = parse_file("fn main() {\n    /* one\n       two */ let a = 1 // three\n}");
+
+    assert!(
+        matches!(errors.as_slice(), [Error::Expected { loc: Loc { row: 3, col: 1 }, .. }]),
+        "found {:?}",
+        errors
+    );
+}
+
+#[test]
+fn escapes_are_decoded() {
+    let (value, errors) = parse_string(r#""a\tb\n\\ \"q\" \u{41}\u{e9}\u{1F600}";"#);
+
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+    assert_eq!(value, "a\tb\n\\ \"q\" Aé\u{1F600}");
+
+    let (value, errors) = parse_string("\"two\nlines é\";");
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+    assert_eq!(value, "two\nlines é");
+}
+
+#[test]
+fn invalid_escapes_are_reported_where_they_are() {
+    let cases = [
+        (r#""ab\q";"#, r"\q", Loc { row: 0, col: 4 }, Span::new(3, 5)),
+        (r#""\u{D800}";"#, r"\u{D800}", Loc { row: 0, col: 2 }, Span::new(1, 9)),
+        (r#""\u{}";"#, r"\u{}", Loc { row: 0, col: 2 }, Span::new(1, 5)),
+        (r#""\u{110000}";"#, r"\u{110000}", Loc { row: 0, col: 2 }, Span::new(1, 11)),
+        (r#""\u{1234567}";"#, r"\u{1234567}", Loc { row: 0, col: 2 }, Span::new(1, 12)),
+        (r#""\u41";"#, r"\u", Loc { row: 0, col: 2 }, Span::new(1, 3)),
+        (r#""\u{41";"#, r"\u{41", Loc { row: 0, col: 2 }, Span::new(1, 6)),
+        (r#""\u{4G}";"#, r"\u{4", Loc { row: 0, col: 2 }, Span::new(1, 5)),
+        // columns count chars and start over after a line break
+        ("\"é\\x\";", r"\x", Loc { row: 0, col: 3 }, Span::new(3, 5)),
+        ("\"one\n  é\\x\";", r"\x", Loc { row: 1, col: 4 }, Span::new(9, 11)),
+    ];
+
+    for (source, escape, loc, span) in cases {
+        assert_eq!(invalid_escape(source), (escape.to_string(), loc, span), "`{source}`");
+    }
+}
@@ -0,0 +1,135 @@
+use xta_lexer::scanner::Scanner;
+use xta_parser::{ast::{Expr, Literal, Stmt, UnaryOpType}, parser::{Error, Parser}};
+
+// every source expression next to the fully-parenthesized form it has to parse into
+const CASES: &[(&str, &str)] = &[
+    // arithmetic
+    ("1 + 2 * 3", "(1 + (2 * 3))"),
+    ("1 * 2 + 3", "((1 * 2) + 3)"),
+    ("a - b - c", "((a - b) - c)"),
+    ("a / b * c", "((a / b) * c)"),
+    ("a % b * c", "((a % b) * c)"),
+    ("a + b % c", "(a + (b % c))"),
+    ("(a + b) * c", "((a + b) * c)"),
+
+    // exponent
+    ("a ** b ** c", "(a ** (b ** c))"),
+    ("a * b ** c", "(a * (b ** c))"),
+    ("a ** b * c", "((a ** b) * c)"),
+    ("-a ** 2", "(-(a ** 2))"),
+    ("a ** -b", "(a ** (-b))"),
+    ("(-a) ** 2", "((-a) ** 2)"),
+
+    // prefix and postfix
+    ("-a * b", "((-a) * b)"),
+    ("- -a", "(-(-a))"),
+    ("!a && b", "((!a) && b)"),
+    ("~a & b", "((~a) & b)"),
+    ("++a + b", "((++a) + b)"),
+    ("a++ + b", "((a++) + b)"),
+    ("-a++", "(-(a++))"),
+    ("a++--", "((a++)--)"),
+
+    // comparison binds tighter than equality
+    ("a < b == c > d", "((a < b) == (c > d))"),
+    ("a == b != c", "((a == b) != c)"),
+    ("a <= b != c >= d", "((a <= b) != (c >= d))"),
+    ("a < b < c", "((a < b) < c)"),
+
+    // logical
+    ("a || b && c", "(a || (b && c))"),
+    ("a && b || c && d", "((a && b) || (c && d))"),
+    ("a == b && c != d", "((a == b) && (c != d))"),
+
+    // bitwise and shifts
+    ("a | b ^ c & d", "(a | (b ^ (c & d)))"),
+    ("a & b == c", "((a & b) == c)"),
+    ("a < b | c", "(a < (b | c))"),
+    ("a << 1 + b", "(a << (1 + b))"),
+    ("a + b << c", "((a + b) << c)"),
+    ("a >> b >> c", "((a >> b) >> c)"),
+
+    // assignment
+    ("a = b = c", "(a = (b = c))"),
+    ("a = b || c", "(a = (b || c))"),
+    ("a += b * 2", "(a += (b * 2))"),
+    ("a -= b -= c", "(a -= (b -= c))"),
+    ("a <<= b = c + 1", "(a <<= (b = (c + 1)))"),
+    ("a = -b ** 2 + c", "(a = ((-(b ** 2)) + c))"),
+
+    // operands
+    ("f(a + b, c * d) ** 2", "(f((a + b), (c * d)) ** 2)"),
+    ("f() + 1.5 * \"s\"", "(f() + (1.5 * \"s\"))"),
+    ("true == None", "(true == None)"),
+];
+
+// assignments whose left side is not a variable
+const INVALID_TARGETS: &[&str] = &[
+    "2 = a",
+    "a + 1 = 2",
+    "(a + b) *= 2",
+    "-a = b",
+    "a = b + 1 = c",
+];
+
+fn parse(source: &str) -> (Option<Stmt<'_>>, Vec<Error<'_>>) {
+    let mut parser = Parser::new(Scanner::new(source));
+    let stmt = parser.parse_statement();
+    (stmt, parser.errors)
+}
+
+fn parenthesize(expr: &Expr) -> String {
+    match expr {
+        Expr::Binary(binary) => format!("({} {} {})", parenthesize(&binary.left), binary.operator, parenthesize(&binary.right)),
+        Expr::Unary(unary) => match unary.operator {
+            UnaryOpType::PostInc | UnaryOpType::PostDec => format!("({}{})", parenthesize(&unary.operand), unary.operator),
+            _ => format!("({}{})", unary.operator, parenthesize(&unary.operand)),
+        },
+        Expr::Literal(literal) => match &literal.value {
+            Literal::Integer(value) => value.to_string(),
+            Literal::Double(value) => value.to_string(),
+            Literal::String(value) => format!("{:?}", value),
+            Literal::Boolean(value) => value.to_string(),
+            Literal::None => "None".to_string(),
+        },
+        Expr::Identifier(id) => id.name.to_string(),
+        Expr::Call(call) => {
+            let args: Vec<String> = call.args.iter().map(parenthesize).collect();
+            format!("{}({})", call.name, args.join(", "))
+        }
+        Expr::Error(_) => "<error>".to_string(),
+    }
+}
+
+#[test]
+fn operators_group_by_precedence_and_associativity() {
+    let mut failures = Vec::new();
+
+    for (source, expected) in CASES {
+        let input = format!("{source};");
+        let found = match parse(&input) {
+            (Some(Stmt::Expr(expr)), errors) if errors.is_empty() => parenthesize(&expr),
+            (stmt, errors) => format!("{:?} with errors {:?}", stmt, errors),
+        };
+
+        if found != *expected {
+            failures.push(format!("`{source}`: expected `{expected}`, found `{found}`"));
+        }
+    }
+
+    assert!(failures.is_empty(), "{} case(s) failed:\n{}", failures.len(), failures.join("\n"));
+}
+
+#[test]
+fn assignment_to_non_variable_is_rejected() {
+    for source in INVALID_TARGETS {
+        let input = format!("{source};");
+        let (_, errors) = parse(&input);
+
+        assert!(
+            matches!(errors.as_slice(), [Error::ExpectedId { .. }]),
+            "`{source}`: expected a single `ExpectedId` error, found {:?}",
+            errors
+        );
+    }
+}
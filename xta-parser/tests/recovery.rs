@@ -0,0 +1,72 @@
+use xta_lexer::scanner::Scanner;
+use xta_parser::{ast::{Expr, Stmt}, parser::{Error, Parser}};
+
+fn parse_body(source: &str) -> (Vec<Stmt<'_>>, Vec<Error<'_>>) {
+    let mut parser = Parser::new(Scanner::new(source));
+    let mut stmts = parser.parse_file();
+
+    match stmts.pop() {
+        Some(Stmt::FunctionDecl(func)) if stmts.is_empty() => (func.body, parser.errors),
+        other => panic!("expected a single function, found {:?}", other),
+    }
+}
+
+#[test]
+fn broken_declaration_keeps_the_next_statement() {
+    let (body, errors) = parse_body("fn main() { let b = (1 + 2; undefined_var; }");
+
+    assert!(matches!(errors.as_slice(), [Error::Expected { .. }]), "unexpected errors: {:?}", errors);
+    assert_eq!(body.len(), 2, "unexpected body: {:?}", body);
+    assert!(matches!(body[0], Stmt::Expr(Expr::Error(_))));
+    assert!(matches!(&body[1], Stmt::Expr(Expr::Identifier(id)) if id.name == "undefined_var"));
+}
+
+#[test]
+fn broken_return_keeps_the_next_statement() {
+    let (body, errors) = parse_body("fn main() { return (1; print(2); }");
+
+    assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+    assert_eq!(body.len(), 2, "unexpected body: {:?}", body);
+    assert!(matches!(&body[1], Stmt::Expr(Expr::Call(call)) if call.name == "print"));
+}
+
+#[test]
+fn deep_nesting_is_reported_instead_of_overflowing() {
+    let sources = [
+        format!("fn main() {{ print({}1{}); }}", "(".repeat(5000), ")".repeat(5000)),
+        format!("fn main() {{ print({}1); }}", "- ".repeat(20000)),
+        format!("fn main() {{ {}{} }}", "if true { ".repeat(20000), " }".repeat(20000)),
+        format!("fn main() {{ let x = 1; x = {}1; }}", "x = ".repeat(20000)),
+        format!("fn main() {{ print({}2); }}", "2 ** ".repeat(20000)),
+        format!("fn main() {{ let x = 1; print(x{}); }}", " + x".repeat(50000)),
+        format!("fn main() {{ let x = 1; print(x{}); }}", "++".repeat(20000)),
+    ];
+
+    for source in &sources {
+        let mut parser = Parser::new(Scanner::new(source));
+        parser.parse_file();
+
+        assert!(
+            matches!(parser.errors.as_slice(), [Error::NestingTooDeep { .. }]),
+            "expected a single `NestingTooDeep` error, found {:?}",
+            parser.errors
+        );
+    }
+}
+
+#[test]
+fn nesting_below_the_limit_is_accepted() {
+    let sources = [
+        format!("fn main() {{ print({}1{}); }}", "(".repeat(200), ")".repeat(200)),
+        format!("fn main() {{ let x = 1; print(x{}); }}", " + x".repeat(200)),
+        format!("fn main() {{ let x = 1; x = {}1; }}", "x = ".repeat(200)),
+        format!("fn main() {{ let x = 1; print(x++{}); }}", " * x++ + -x".repeat(100)),
+    ];
+
+    for source in &sources {
+        let mut parser = Parser::new(Scanner::new(source));
+        parser.parse_file();
+
+        assert!(parser.errors.is_empty(), "unexpected errors: {:?}", parser.errors);
+    }
+}
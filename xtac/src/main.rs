@@ -1,40 +1,108 @@
-use clap::Parser as p;
-use std::{fs, path::PathBuf, process::exit, time::Instant};
-use xta_lexer::scanner::Scanner;
+use clap::{Parser as p, ValueEnum};
+use std::{env, fs, io::{stderr, IsTerminal}, path::PathBuf, process::exit};
+use xta_analyzer::analyzer::Analyzer;
+use xta_diagnostics::{diagnostic::Diagnostic, json, renderer::Renderer};
+use xta_interpreter::interpreter::Interpreter;
+use xta_lexer::{line_index::LineIndex, scanner::Scanner};
 use xta_parser::parser::Parser;
 
 
 #[derive(p)]
 #[command(name = "Xta", version, about, long_about = None)]
 pub enum Cli {
-    Run { path: PathBuf },
+    /// Checks an `.xta` source file and runs it
+    Run {
+        /// The source file to run
+        path: PathBuf,
+        /// Whether diagnostics are colored, `auto` colors them only on a terminal
+        #[arg(long, value_enum, default_value_t = Color::Auto)]
+        color: Color,
+        /// `json` writes every diagnostic and runtime error to stderr as a single line JSON object,
+        /// keeping them apart from what the program prints
+        #[arg(long, value_enum, default_value_t = MessageFormat::Human)]
+        message_format: MessageFormat,
+    },
+    /// Prints a long-form explanation of an error code, e.g. `xtac explain XP0001`
+    Explain { code: String },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum MessageFormat {
+    Human,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Color {
+    Auto,
+    Always,
+    Never,
+}
+
+impl Color {
+    fn enabled(self) -> bool {
+        match self {
+            Color::Auto => stderr().is_terminal() && env::var_os("NO_COLOR").is_none(),
+            Color::Always => true,
+            Color::Never => false,
+        }
+    }
 }
 
 fn main() {
     let cli = Cli::parse();
 
     match cli {
-        Cli::Run { path } => {
+        Cli::Explain { code } => {
+            let code = code.to_uppercase();
+            match xta_parser::explain::explain(&code).or_else(|| xta_analyzer::explain::explain(&code)) {
+                Some(text) => print!("{}", text),
+                None => {
+                    eprintln!("~ Error: `{}` is not a known error code.", code);
+                    exit(1);
+                }
+            }
+        }
+        Cli::Run { path, color, message_format } => {
             if !path.is_file() {
                 eprintln!("~ Error: Cannot read the specified source file.");
                 exit(1);
             }
-            match fs::read_to_string(path) {
+            match fs::read_to_string(&path) {
                 Ok(content) => {
                     let scanner = Scanner::new(&content);
                     let mut parser = Parser::new(scanner);
 
-                    let start = Instant::now();
                     let stmts = parser.parse_file();
-                    let elapsed = start.elapsed();
 
-                    println!("parsing: {:?}", elapsed);
-                    for stmt in stmts {
-                        println!("{:?}", stmt);
+                    let analyzer = Analyzer::new(&stmts);
+
+                    let diagnostics: Vec<Diagnostic> = parser.errors.iter().map(|error| error.to_diagnostic())
+                        .chain(analyzer.errors.iter().map(|error| error.to_diagnostic()))
+                        .chain(analyzer.warnings.iter().map(|warning| warning.to_diagnostic()))
+                        .collect();
+
+                    let renderer = Renderer::new(color.enabled());
+                    let index = LineIndex::new(&content);
+                    let path = path.display().to_string();
+                    for diagnostic in &diagnostics {
+                        match message_format {
+                            MessageFormat::Human => eprintln!("{}", renderer.render(diagnostic, &path, &index)),
+                            MessageFormat::Json => eprintln!("{}", json::to_json(diagnostic, &path, &index)),
+                        }
+                    }
+
+                    if !parser.errors.is_empty() || !analyzer.errors.is_empty() {
+                        exit(1);
                     }
 
-                    for error in parser.errors {
-                        eprintln!("{}", error);
+                    let mut interpreter = Interpreter::new(stmts);
+                    if let Err(error) = interpreter.run() {
+                        match message_format {
+                            MessageFormat::Human => eprintln!("{}", error),
+                            MessageFormat::Json => eprintln!("{}", json::to_json(&error.to_diagnostic(&index), &path, &index)),
+                        }
+                        exit(1);
                     }
                 }
                 Err(e) => {